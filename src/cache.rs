@@ -0,0 +1,260 @@
+//! Persistent caches: downloaded JDK archives, kept in `Config.download_dir`,
+//! and fetched version listings, kept under the config directory
+
+use crate::downloader::AvailableVersion;
+use crate::lock::FileLock;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the file an interrupted download is resumed from
+const PARTIAL_FILE: &str = "download.part";
+
+/// File next to a cached archive recording the URL it was downloaded from
+const SOURCE_FILE: &str = ".source-url";
+
+/// How version listings use the metadata cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Use cached listings younger than the TTL, fetch otherwise
+    #[default]
+    Default,
+    /// Always fetch, then update the cache
+    Refresh,
+    /// Never touch the network: use cached listings and archives of any age
+    Offline,
+}
+
+/// Archives stored as `<dir>/<key>/<file name>`, where the key is the archive's
+/// SHA-256 checksum (or a hash of its URL when no checksum is published)
+pub struct ArchiveCache {
+    dir: PathBuf,
+}
+
+/// An archive, or an unfinished download, found in the cache
+#[derive(Debug, Clone)]
+pub struct CachedArchive {
+    pub key: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+    /// Whether this is an interrupted download that can still be resumed
+    pub partial: bool,
+}
+
+impl ArchiveCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Lock held while the archive under `key` is downloaded
+    pub fn lock_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.lock", key))
+    }
+
+    /// Cache key for an archive: its checksum when known, otherwise its URL.
+    /// Keys name directories, so checksums other than 64 hex digits, which
+    /// come from remote metadata or the command line, are refused.
+    pub fn key(url: &str, checksum: Option<&str>) -> Result<String> {
+        match checksum {
+            Some(checksum) => {
+                if checksum.len() != 64 || !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+                    anyhow::bail!(
+                        "Invalid SHA-256 checksum '{}' for {} (expected 64 hex digits)",
+                        checksum,
+                        url
+                    );
+                }
+                Ok(checksum.to_lowercase())
+            }
+            None => Ok(Self::url_key(url)),
+        }
+    }
+
+    fn url_key(url: &str) -> String {
+        format!("url-{}", hex::encode(Sha256::digest(url.as_bytes())))
+    }
+
+    /// Directory holding the archive stored under `key`
+    pub fn entry_dir(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+
+    /// Path a download for `key` is written to until it completes
+    pub fn partial_path(&self, key: &str) -> PathBuf {
+        self.entry_dir(key).join(PARTIAL_FILE)
+    }
+
+    /// The completed archive stored under `key`, if any
+    pub fn lookup(&self, key: &str) -> Option<PathBuf> {
+        fs::read_dir(self.entry_dir(key))
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .find(|path| path.is_file() && Self::is_archive(path))
+    }
+
+    /// The completed archive downloaded from `url`, whatever key it is stored under
+    pub fn lookup_url(&self, url: &str) -> Option<PathBuf> {
+        if let Some(archive) = self.lookup(&Self::url_key(url)) {
+            return Some(archive);
+        }
+
+        fs::read_dir(&self.dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                fs::read_to_string(entry.path().join(SOURCE_FILE)).is_ok_and(|source| source == url)
+            })
+            .find_map(|entry| self.lookup(&entry.file_name().to_string_lossy()))
+    }
+
+    /// Remember which URL the archive stored under `key` came from
+    pub fn record_source(&self, key: &str, url: &str) -> Result<()> {
+        fs::write(self.entry_dir(key).join(SOURCE_FILE), url)?;
+        Ok(())
+    }
+
+    fn is_archive(path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| name != PARTIAL_FILE && name != SOURCE_FILE)
+    }
+
+    /// Promote a finished download to a cached archive named `file_name`
+    pub fn complete(&self, key: &str, file_name: &str) -> Result<PathBuf> {
+        let dest = self.entry_dir(key).join(file_name);
+        fs::rename(self.partial_path(key), &dest)?;
+        Ok(dest)
+    }
+
+    /// Drop everything stored under `key`, e.g. after a checksum mismatch
+    pub fn evict(&self, key: &str) -> Result<()> {
+        let dir = self.entry_dir(key);
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Every cached archive and unfinished download, largest first
+    pub fn entries(&self) -> Result<Vec<CachedArchive>> {
+        let mut entries = Vec::new();
+        if !self.dir.is_dir() {
+            return Ok(entries);
+        }
+
+        for entry_dir in fs::read_dir(&self.dir)? {
+            let entry_dir = entry_dir?;
+            if !entry_dir.file_type()?.is_dir() {
+                continue;
+            }
+            let key = entry_dir.file_name().to_string_lossy().to_string();
+
+            for file in fs::read_dir(entry_dir.path())? {
+                let file = file?;
+                let metadata = file.metadata()?;
+                if !metadata.is_file() || file.file_name() == SOURCE_FILE {
+                    continue;
+                }
+
+                entries.push(CachedArchive {
+                    key: key.clone(),
+                    path: file.path(),
+                    size: metadata.len(),
+                    modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                    partial: file.file_name() == PARTIAL_FILE,
+                });
+            }
+        }
+
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.size));
+        Ok(entries)
+    }
+
+    /// Remove cached archives, or only unfinished downloads, returning the
+    /// entries removed. Archives another jaman process is downloading are
+    /// kept.
+    pub fn clean(&self, partial_only: bool) -> Result<Vec<CachedArchive>> {
+        let mut removed = Vec::new();
+
+        for entry in self.entries()? {
+            if partial_only && !entry.partial {
+                continue;
+            }
+            let Some(_lock) = FileLock::try_exclusive(&self.lock_path(&entry.key))? else {
+                continue;
+            };
+            fs::remove_file(&entry.path)?;
+            if !partial_only {
+                let _ = fs::remove_file(self.entry_dir(&entry.key).join(SOURCE_FILE));
+            }
+            // Drop the key directory once it is empty
+            let _ = fs::remove_dir(self.entry_dir(&entry.key));
+            removed.push(entry);
+        }
+
+        Ok(removed)
+    }
+}
+
+/// Version listings fetched from the providers, stored as JSON files
+pub struct MetadataCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+/// A listing as stored by `MetadataCache`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedMetadata {
+    pub fetched_at: DateTime<Utc>,
+    pub versions: Vec<AvailableVersion>,
+}
+
+impl MetadataCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// The listing stored under `key`, however old it is
+    pub fn load(&self, key: &str) -> Option<CachedMetadata> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// The listing stored under `key` if it is younger than the TTL
+    pub fn load_fresh(&self, key: &str) -> Option<CachedMetadata> {
+        self.load(key)
+            .filter(|cached| Utc::now() - cached.fetched_at < self.ttl)
+    }
+
+    pub fn store(&self, key: &str, versions: &[AvailableVersion]) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let cached = CachedMetadata {
+            fetched_at: Utc::now(),
+            versions: versions.to_vec(),
+        };
+
+        // Write next to the target first so readers never see a torn file
+        let path = self.path(key);
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_string(&cached)?)?;
+        fs::rename(temp, path)?;
+
+        Ok(())
+    }
+}
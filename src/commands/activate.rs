@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::path_manager::PathManager;
+use crate::shell::JAMAN_SHELL;
 use anyhow::Result;
 use console::style;
 use dialoguer::Select;
@@ -8,7 +9,7 @@ pub struct ActivateCommand;
 
 impl ActivateCommand {
     pub async fn execute(version_query: Option<String>) -> Result<()> {
-        let mut config = Config::load()?;
+        let config = Config::load()?;
 
         if config.installed_versions.is_empty() {
             println!("{}", style("No Java versions installed.").yellow());
@@ -21,13 +22,7 @@ impl ActivateCommand {
 
         // Select version
         let selected_version = if let Some(query) = version_query {
-            // Find matching version
-            config
-                .installed_versions
-                .iter()
-                .find(|v| v.version.contains(&query))
-                .ok_or_else(|| anyhow::anyhow!("Version {} not found", query))?
-                .clone()
+            config.get_version(&query)?.clone()
         } else {
             // Interactive selection
             let version_names: Vec<String> = config
@@ -35,7 +30,7 @@ impl ActivateCommand {
                 .iter()
                 .map(|v| {
                     let lts = if v.is_lts { " [LTS]" } else { "" };
-                    let active = if config.active_version.as_ref() == Some(&v.version) {
+                    let active = if config.active_id.as_ref() == Some(&v.id) {
                         " (active)"
                     } else {
                         ""
@@ -59,17 +54,22 @@ impl ActivateCommand {
             style(&selected_version.version).cyan()
         );
 
-        // Set PATH
-        PathManager::set_active_java(&selected_version.path)?;
+        // Set PATH, unless the shell hook switches versions at every prompt
+        let hooked = !cfg!(windows) && std::env::var_os(JAMAN_SHELL).is_some();
+        if !hooked {
+            PathManager::set_active_java(&selected_version.path)?;
+        }
 
-        // Update config
-        config.set_active(&selected_version.version)?;
+        // Update config, reloaded under the lock to keep concurrent changes
+        let _lock = Config::lock_async().await?;
+        let mut config = Config::load()?;
+        config.set_active(&selected_version.id)?;
 
         // Mark as used
         if let Some(version) = config
             .installed_versions
             .iter_mut()
-            .find(|v| v.version == selected_version.version)
+            .find(|v| v.id == selected_version.id)
         {
             version.mark_used();
         }
@@ -93,6 +93,14 @@ impl ActivateCommand {
             style(selected_version.java_executable().display()).cyan()
         );
 
+        if hooked {
+            println!(
+                "\n{}",
+                style("The shell hook applies it at the next prompt, unless a project version file overrides it.")
+                    .dim()
+            );
+        }
+
         if cfg!(windows) {
             println!(
                 "\n{}",
@@ -0,0 +1,85 @@
+use crate::cache::ArchiveCache;
+use crate::commands::CleanCommand;
+use crate::config::Config;
+use anyhow::Result;
+use console::style;
+
+pub struct CacheCommand;
+
+impl CacheCommand {
+    /// Show the archives kept in the download directory
+    pub fn list() -> Result<()> {
+        let config = Config::load()?;
+        let cache = ArchiveCache::new(&config.download_dir);
+        let entries = cache.entries()?;
+
+        if entries.is_empty() {
+            println!("{}", style("The download cache is empty.").yellow());
+            return Ok(());
+        }
+
+        println!(
+            "{} {}\n",
+            style("Cached archives in").bold().green(),
+            style(cache.dir().display()).cyan()
+        );
+
+        let mut total_size = 0;
+        for entry in &entries {
+            total_size += entry.size;
+
+            let name = entry
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let partial = if entry.partial {
+                style(" [incomplete]").yellow()
+            } else {
+                style("")
+            };
+            let modified = entry
+                .modified
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+
+            println!(
+                "  {}{} - {} - {}",
+                style(name).white(),
+                partial,
+                style(CleanCommand::format_size(entry.size)).cyan(),
+                style(modified).dim()
+            );
+        }
+
+        println!(
+            "\n{} {}",
+            style("Total space:").bold(),
+            style(CleanCommand::format_size(total_size)).cyan().bold()
+        );
+
+        Ok(())
+    }
+
+    /// Delete cached archives, or only interrupted downloads
+    pub fn clean(partial_only: bool) -> Result<()> {
+        let config = Config::load()?;
+        let cache = ArchiveCache::new(&config.download_dir);
+        let removed = cache.clean(partial_only)?;
+
+        if removed.is_empty() {
+            println!("{}", style("Nothing to remove.").green());
+            return Ok(());
+        }
+
+        let freed: u64 = removed.iter().map(|entry| entry.size).sum();
+        println!(
+            "{} {} cached file(s), {} freed",
+            style("Removed").green().bold(),
+            style(removed.len()).cyan().bold(),
+            style(CleanCommand::format_size(freed)).cyan().bold()
+        );
+
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ pub struct CleanCommand;
 
 impl CleanCommand {
     pub async fn execute(days: Option<i64>, force: bool) -> Result<()> {
-        let mut config = Config::load()?;
+        let config = Config::load()?;
         let days_threshold = days.unwrap_or(90);
 
         println!(
@@ -24,6 +24,13 @@ impl CleanCommand {
         let threshold_date = Utc::now() - Duration::days(days_threshold);
         let mut unused_versions = Vec::new();
 
+        // Early-access builds expire as soon as a GA or newer EA build replaces them
+        let superseded: Vec<_> = config
+            .superseded_early_access()
+            .into_iter()
+            .map(|v| v.path.clone())
+            .collect();
+
         for version in &config.installed_versions {
             // Skip auto-detected versions (we don't manage their files)
             if version.auto_detected {
@@ -31,7 +38,7 @@ impl CleanCommand {
             }
 
             // Skip active version
-            if config.active_version.as_ref() == Some(&version.version) {
+            if config.active_id.as_ref() == Some(&version.id) {
                 continue;
             }
 
@@ -41,7 +48,7 @@ impl CleanCommand {
                 None => true, // Never used
             };
 
-            if is_unused {
+            if is_unused || superseded.contains(&version.path) {
                 unused_versions.push(version.clone());
             }
         }
@@ -61,6 +68,9 @@ impl CleanCommand {
 
         for version in &unused_versions {
             let last_used_str = match version.last_used {
+                _ if superseded.contains(&version.path) => {
+                    "superseded early-access build".to_string()
+                }
                 Some(date) => format!("last used {}", date.format("%Y-%m-%d")),
                 None => "never used".to_string(),
             };
@@ -102,6 +112,9 @@ impl CleanCommand {
 
         println!("\n{}", style("Removing installations...").bold());
 
+        let _lock = Config::lock_async().await?;
+        let mut config = Config::load()?;
+
         let mut removed_count = 0;
         let mut failed_count = 0;
 
@@ -111,7 +124,7 @@ impl CleanCommand {
             match fs::remove_dir_all(&version.path) {
                 Ok(_) => {
                     println!("{}", style("✓").green());
-                    config.remove_version(&version.path);
+                    config.remove_version(&version.id);
                     removed_count += 1;
                 }
                 Err(e) => {
@@ -162,7 +175,7 @@ impl CleanCommand {
         Ok(total_size)
     }
 
-    fn format_size(bytes: u64) -> String {
+    pub fn format_size(bytes: u64) -> String {
         const KB: u64 = 1024;
         const MB: u64 = KB * 1024;
         const GB: u64 = MB * 1024;
@@ -0,0 +1,51 @@
+use crate::config::Config;
+use crate::project;
+use crate::shell::{self, CurrentEnv, EnvChange, JAMAN_SESSION_HOME, Shell};
+use anyhow::Result;
+
+pub struct EnvCommand;
+
+impl EnvCommand {
+    /// Print the statements that put `spec`, or the version in effect in the
+    /// current directory, on `JAVA_HOME` and `PATH` for one shell session.
+    /// Runs from rc files and prompts, so it only reads the config: no lock to
+    /// wait for, and no writes rotating the config backups.
+    pub fn execute(spec: Option<String>, shell: Option<String>) -> Result<()> {
+        let shell = match shell {
+            Some(shell) => shell.parse()?,
+            None => Shell::detect(),
+        };
+        let config = Config::load()?;
+
+        let version = match &spec {
+            Some(spec) => config.get_version(spec.trim())?,
+            None => match project::effective_version(&config, &std::env::current_dir()?)? {
+                Some((version, _)) => version,
+                None => anyhow::bail!(
+                    "No Java version is active here; pass one, e.g. 'jaman env 21', or run 'jaman activate'"
+                ),
+            },
+        };
+
+        let mut current = CurrentEnv::from_process();
+        // A JAVA_HOME exported by an rc file that `jaman activate` edited is
+        // also jaman's, so its bin directory is replaced rather than shadowed
+        if current.jaman_java_home.is_none() {
+            current.jaman_java_home = current.java_home.clone().filter(|home| {
+                config
+                    .installed_versions
+                    .iter()
+                    .any(|installed| installed.path.as_os_str() == home.as_str())
+            });
+        }
+
+        let mut changes = shell::activation_changes(&version.path, &current)?;
+        // Keeps the shell hook from switching away at the next prompt
+        changes.push(EnvChange {
+            name: JAMAN_SESSION_HOME.to_string(),
+            value: Some(version.path.to_string_lossy().into_owned()),
+        });
+        println!("{}", shell::render(shell, &changes));
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::config::Config;
+use crate::project;
+use crate::shell::{self, CurrentEnv, Shell};
+use anyhow::{Context, Result};
+use console::style;
+
+pub struct HookCommand;
+
+/// Remembers the last error the hook reported, so a broken project file is
+/// reported once rather than at every prompt
+const HOOK_ERROR: &str = "JAMAN_HOOK_ERROR";
+
+impl HookCommand {
+    /// Print the prompt hook for `shell`, to be evaluated in its rc file
+    pub fn execute(shell: &str) -> Result<()> {
+        let shell: Shell = shell.parse()?;
+        let jaman = std::env::current_exe().context("Failed to locate the jaman executable")?;
+        print!("{}", shell::hook(shell, &jaman)?);
+        Ok(())
+    }
+
+    /// Print the statements that switch the calling shell to the version in
+    /// effect in the current directory. Runs before every prompt, so it stays
+    /// quiet unless something changed.
+    pub fn export(shell: &str) -> Result<()> {
+        let shell: Shell = shell.parse()?;
+        let current = CurrentEnv::from_process();
+
+        let (java_home, error) = match &current.session_java_home {
+            // `jaman env` chose a JDK for this session
+            Some(session) => (Some(session.into()), None),
+            None => match Self::effective_java_home() {
+                Ok(java_home) => (java_home, None),
+                // Keep the shell on its current JDK until the problem is fixed
+                Err(e) => (
+                    current.jaman_java_home.as_ref().map(Into::into),
+                    Some(format!("{:#}", e)),
+                ),
+            },
+        };
+
+        let mut changes = shell::java_home_changes(java_home.as_deref(), &current)?;
+        let previous_error = std::env::var(HOOK_ERROR).ok();
+        if error != previous_error {
+            if let Some(error) = &error {
+                eprintln!("{} {}", style("jaman:").yellow().bold(), error);
+            }
+            changes.push(shell::EnvChange {
+                name: HOOK_ERROR.to_string(),
+                value: error,
+            });
+        }
+
+        println!("{}", shell::render(shell, &changes));
+        Ok(())
+    }
+
+    fn effective_java_home() -> Result<Option<std::path::PathBuf>> {
+        let dir = std::env::current_dir()?;
+        let config = Config::load()?;
+        Ok(project::effective_version(&config, &dir)?.map(|(version, _)| version.path.clone()))
+    }
+}
@@ -1,38 +1,79 @@
-use crate::config::{Config, JavaVersion};
-use crate::downloader::Downloader;
+use crate::cache::CacheMode;
+use crate::config::{Channel, Config, ImageType, JavaVersion};
+use crate::detector::JavaDetector;
+use crate::downloader::{AvailableVersion, Downloader};
+use crate::query::VersionQuery;
 use anyhow::Result;
 use console::style;
 use dialoguer::Select;
+use std::fs;
+use std::path::PathBuf;
 
 pub struct InstallCommand;
 
+/// Where `jaman install --from-file/--from-url` takes its archive from
+pub enum ArchiveSource {
+    File(PathBuf),
+    Url(String),
+}
+
 impl InstallCommand {
-    pub async fn execute(version_query: Option<String>) -> Result<()> {
-        let mut config = Config::load()?;
-        let downloader = Downloader::new();
+    pub async fn execute(
+        version_query: Option<String>,
+        image_type: ImageType,
+        channel: Channel,
+        javafx: bool,
+        cache_mode: CacheMode,
+    ) -> Result<()> {
+        let config = Config::load()?;
+        let downloader = Downloader::from_config(&config)?
+            .with_image_type(image_type)
+            .with_channel(channel)
+            .with_cache_mode(cache_mode);
+        Self::clean_interrupted(&config)?;
 
         println!("{}", style("Fetching available versions...").dim());
-        let available_versions = downloader.fetch_available_versions().await?;
+        let report = downloader.fetch_available_versions().await?;
+        report.warn_missing();
+        report.warn_cached();
+        let mut available_versions = report.versions;
+        available_versions.sort_by(|a, b| b.cmp_version(a));
+        if javafx {
+            available_versions.retain(|v| v.javafx_bundled);
+        }
 
         if available_versions.is_empty() {
-            anyhow::bail!("No versions available for download");
+            match channel {
+                _ if javafx => anyhow::bail!("No {} builds bundling JavaFX available", image_type),
+                Channel::Ga => anyhow::bail!("No {} versions available for download", image_type),
+                Channel::Ea => anyhow::bail!("No early-access {} builds available", image_type),
+            }
         }
 
         // Select version
         let selected_version = if let Some(query) = version_query {
-            // Find matching version
-            available_versions
-                .iter()
-                .find(|v| v.version.contains(&query))
-                .ok_or_else(|| anyhow::anyhow!("Version {} not found", query))?
-                .clone()
+            Self::resolve_query(&downloader, &available_versions, &query, javafx).await?
         } else {
             // Interactive selection
             let version_names: Vec<String> = available_versions
                 .iter()
                 .map(|v| {
                     let lts = if v.is_lts { " [LTS]" } else { "" };
-                    format!("{} - {}{}", v.version, v.vendor, lts)
+                    let ea = if v.channel == Channel::Ea {
+                        " [EA]"
+                    } else {
+                        ""
+                    };
+                    let javafx = if v.javafx_bundled { " [JavaFX]" } else { "" };
+                    let image = v
+                        .image_type
+                        .badge()
+                        .map(|badge| format!(" [{}]", badge))
+                        .unwrap_or_default();
+                    format!(
+                        "{} - {}{}{}{}{}",
+                        v.version, v.vendor, lts, ea, javafx, image
+                    )
                 })
                 .collect();
 
@@ -46,12 +87,16 @@ impl InstallCommand {
         };
 
         // Check if already installed
-        if config.get_version(&selected_version.version).is_some() {
+        if let Some(installed) = config
+            .installed_versions
+            .iter()
+            .find(|v| selected_version.is_installed_as(v))
+        {
             println!(
                 "{}",
                 style(format!(
                     "Version {} is already installed",
-                    selected_version.version
+                    installed.version
                 ))
                 .yellow()
             );
@@ -70,31 +115,174 @@ impl InstallCommand {
             .download_and_install(&selected_version, &config.installation_dir)
             .await?;
 
+        // Oracle GraalVM is listed by feature release only; the JDK's release
+        // file has the exact version
+        let version = if selected_version.is_feature_only() {
+            JavaDetector::inspect(&jdk_path)?.version
+        } else {
+            selected_version.version.clone()
+        };
+
         // Add to config
-        let java_version = JavaVersion::new(
-            selected_version.version.clone(),
+        let mut java_version = JavaVersion::new(
+            version.clone(),
             selected_version.vendor.clone(),
             jdk_path,
             selected_version.is_lts,
             selected_version.architecture.clone(),
             false,
         );
+        java_version.javafx_bundled = selected_version.javafx_bundled;
+        java_version.image_type = selected_version.image_type;
+        java_version.channel = selected_version.channel;
+        java_version.native_image = JavaDetector::has_native_image(&java_version.path);
 
-        config.add_version(java_version);
+        // Reload under the lock so versions installed meanwhile are kept
+        let _lock = Config::lock_async().await?;
+        let mut config = Config::load()?;
+        let id = config.add_version(java_version);
         config.save()?;
 
         println!(
             "\n{} Successfully installed {} ✓",
             style("✓").green().bold(),
-            style(&selected_version.version).cyan().bold()
+            style(&version).cyan().bold()
+        );
+
+        println!(
+            "\n{} {}",
+            style("Use").dim(),
+            style(format!("jaman activate {}", id)).cyan()
+        );
+
+        Ok(())
+    }
+
+    /// Install a JDK archive from outside the provider list, identifying its
+    /// version and vendor from the unpacked files
+    pub async fn execute_archive(source: ArchiveSource, sha256: Option<String>) -> Result<()> {
+        let config = Config::load()?;
+        let downloader = Downloader::from_config(&config)?;
+        fs::create_dir_all(&config.installation_dir)?;
+        Self::clean_interrupted(&config)?;
+
+        let jdk_path = match &source {
+            ArchiveSource::File(archive) => {
+                println!(
+                    "\n{} {}...\n",
+                    style("Installing").green().bold(),
+                    style(archive.display()).cyan()
+                );
+                downloader.install_archive(archive, sha256.as_deref(), &config.installation_dir)?
+            }
+            ArchiveSource::Url(url) => {
+                println!(
+                    "\n{} {}...\n",
+                    style("Installing").green().bold(),
+                    style(url).cyan()
+                );
+                downloader
+                    .install_from_url(url, sha256.as_deref(), &config.installation_dir)
+                    .await?
+            }
+        };
+
+        // The directory the archive was unpacked into, which may wrap the JDK root
+        let extract_dir = jdk_path
+            .ancestors()
+            .find(|dir| dir.parent() == Some(config.installation_dir.as_path()))
+            .unwrap_or(&jdk_path)
+            .to_path_buf();
+
+        let java_version = match JavaDetector::inspect(&jdk_path) {
+            Ok(java_version) => java_version,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&extract_dir);
+                return Err(e.context("The archive does not contain a recognizable JDK"));
+            }
+        };
+
+        if sha256.is_none() {
+            println!(
+                "{}",
+                style("No --sha256 given, the archive was not verified").yellow()
+            );
+        }
+
+        let version = java_version.version.clone();
+        let vendor = java_version.vendor.clone();
+        let _lock = Config::lock_async().await?;
+        let mut config = Config::load()?;
+        let id = config.add_version(java_version);
+        config.save()?;
+
+        println!(
+            "\n{} Successfully installed {} ({}) ✓",
+            style("✓").green().bold(),
+            style(&version).cyan().bold(),
+            style(&vendor).dim()
         );
 
         println!(
             "\n{} {}",
             style("Use").dim(),
-            style(format!("jaman activate {}", selected_version.version)).cyan()
+            style(format!("jaman activate {}", id)).cyan()
         );
 
         Ok(())
     }
+
+    /// Remove leftovers of installs that were interrupted before completing
+    fn clean_interrupted(config: &Config) -> Result<()> {
+        let removed = Downloader::clean_staging(&config.installation_dir)?;
+        if removed > 0 {
+            println!(
+                "{}",
+                style(format!(
+                    "Removed {} unfinished installation(s) left by an interrupted install",
+                    removed
+                ))
+                .dim()
+            );
+        }
+        Ok(())
+    }
+
+    /// Find the newest version a query refers to. When several vendors ship
+    /// that version the query is ambiguous, and the error lists them so a
+    /// vendor can be added. A patch release ("17.0.6") that is no longer the
+    /// latest is looked up in the release history. With `javafx` only builds
+    /// bundling JavaFX qualify.
+    async fn resolve_query(
+        downloader: &Downloader,
+        available_versions: &[AvailableVersion],
+        query: &str,
+        javafx: bool,
+    ) -> Result<AvailableVersion> {
+        let query: VersionQuery = query.parse()?;
+        if let Some(version) = query.select(AvailableVersion::distinct_builds(
+            available_versions,
+            javafx,
+        ))? {
+            return Ok(version.clone());
+        }
+
+        let major = query
+            .feature()
+            .filter(|_| query.is_patch())
+            .ok_or_else(|| anyhow::anyhow!("Version {} not found", query))?;
+
+        println!(
+            "{}",
+            style(format!("Searching all Java {} releases...", major)).dim()
+        );
+
+        let history = downloader.fetch_release_history(major).await?;
+        history.warn_missing();
+        history.warn_cached();
+        query
+            .select(AvailableVersion::distinct_builds(&history.versions, javafx))?
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Version {} not found", query))
+    }
 }
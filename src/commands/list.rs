@@ -1,26 +1,40 @@
-use crate::config::Config;
+use crate::cache::CacheMode;
+use crate::config::{Channel, Config, ImageType};
 use crate::downloader::Downloader;
+use crate::query::VersionQuery;
 use anyhow::Result;
 use console::style;
 
 pub struct ListCommand;
 
+/// Filters accepted by `jaman list`
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    pub filter: Option<String>,
+    pub available: bool,
+    pub lts: bool,
+    pub graalvm: bool,
+    pub javafx: bool,
+    /// Every GA release of the filtered feature version, not only the latest
+    pub all_releases: bool,
+    pub jre: bool,
+    /// Early-access builds instead of GA releases
+    pub early_access: bool,
+    /// Whether to use, bypass or rely solely on the cached version list
+    pub cache_mode: CacheMode,
+}
+
 impl ListCommand {
-    pub async fn execute(
-        filter: Option<String>,
-        available: bool,
-        lts: bool,
-        graalvm: bool,
-    ) -> Result<()> {
-        if available {
-            Self::list_available(filter, lts, graalvm).await?;
+    pub async fn execute(options: ListOptions) -> Result<()> {
+        if options.available {
+            Self::list_available(&options).await?;
         } else {
-            Self::list_installed(filter, lts)?;
+            Self::list_installed(&options)?;
         }
         Ok(())
     }
 
-    fn list_installed(filter: Option<String>, lts_only: bool) -> Result<()> {
+    fn list_installed(options: &ListOptions) -> Result<()> {
         let config = Config::load()?;
 
         if config.installed_versions.is_empty() {
@@ -37,19 +51,32 @@ impl ListCommand {
         let mut versions = config.installed_versions.clone();
 
         // Apply filters
-        if let Some(ref filter_str) = filter {
-            versions.retain(|v| v.version.contains(filter_str));
+        if let Some(filter) = &options.filter {
+            let query: VersionQuery = filter.parse()?;
+            versions.retain(|v| query.matches(v));
         }
 
-        if lts_only {
+        if options.lts {
             versions.retain(|v| v.is_lts);
         }
 
-        // Sort by version
-        versions.sort_by(|a, b| b.version.cmp(&a.version));
+        if options.graalvm {
+            versions.retain(|v| v.native_image || v.vendor.to_lowercase().contains("graalvm"));
+        }
+
+        if options.jre {
+            versions.retain(|v| v.image_type == ImageType::Jre);
+        }
+
+        if options.early_access {
+            versions.retain(|v| v.channel == Channel::Ea);
+        }
+
+        // Newest first
+        versions.sort_by(|a, b| b.cmp_version(a));
 
         for version in versions {
-            let is_active = config.active_version.as_ref() == Some(&version.version);
+            let is_active = config.active_id.as_ref() == Some(&version.id);
 
             let status_icon = if is_active {
                 style("●").green().bold()
@@ -69,6 +96,29 @@ impl ListCommand {
                 style("")
             };
 
+            let javafx_badge = if version.javafx_bundled {
+                style(" [JavaFX]").magenta()
+            } else {
+                style("")
+            };
+
+            let image_badge = match version.image_type.badge() {
+                Some(badge) => style(format!(" [{}]", badge)).blue(),
+                None => style(String::new()),
+            };
+
+            let ea_badge = if version.channel == Channel::Ea {
+                style(" [EA]").red()
+            } else {
+                style("")
+            };
+
+            let native_image_badge = if version.native_image {
+                style(" [native-image]").yellow()
+            } else {
+                style("")
+            };
+
             let auto_detected = if version.auto_detected {
                 style(" (auto-detected)").dim()
             } else {
@@ -76,21 +126,27 @@ impl ListCommand {
             };
 
             println!(
-                "  {} {} - {}{}{} - {}",
+                "  {} {} - {}{}{}{}{}{}{} - {} {}",
                 status_icon,
                 version_str,
                 style(&version.vendor).dim(),
                 lts_badge,
+                ea_badge,
+                image_badge,
+                javafx_badge,
+                native_image_badge,
                 auto_detected,
-                style(version.path.display()).dim()
+                style(version.path.display()).dim(),
+                style(format!("[{}]", version.id)).dim()
             );
         }
 
-        if let Some(active) = config.active_version {
+        if let Some(active) = config.active() {
             println!(
-                "\n{} {}",
+                "\n{} {} ({})",
                 style("Active version:").bold(),
-                style(active).green()
+                style(&active.version).green(),
+                style(&active.id).dim()
             );
         } else {
             println!("\n{}", style("No active version set.").yellow());
@@ -99,29 +155,68 @@ impl ListCommand {
         Ok(())
     }
 
-    async fn list_available(
-        filter: Option<String>,
-        lts_only: bool,
-        graalvm_only: bool,
-    ) -> Result<()> {
+    async fn list_available(options: &ListOptions) -> Result<()> {
         println!("{}", style("Fetching available versions...").dim());
 
-        let downloader = Downloader::new();
-        let mut versions = downloader.fetch_available_versions().await?;
+        let config = Config::load()?;
+        let image_type = if options.jre {
+            ImageType::Jre
+        } else {
+            ImageType::Jdk
+        };
+        let channel = if options.early_access {
+            Channel::Ea
+        } else {
+            Channel::Ga
+        };
+        let downloader = Downloader::from_config(&config)?
+            .with_image_type(image_type)
+            .with_channel(channel)
+            .with_cache_mode(options.cache_mode);
+
+        let query = options
+            .filter
+            .as_deref()
+            .map(str::parse::<VersionQuery>)
+            .transpose()?;
+
+        let mut versions = if options.all_releases {
+            let major = query
+                .as_ref()
+                .and_then(VersionQuery::feature)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "--all needs a feature version, e.g. 'jaman list --available 17 --all'"
+                    )
+                })?;
+            let report = downloader.fetch_release_history(major).await?;
+            report.warn_missing();
+            report.warn_cached();
+            report.versions
+        } else {
+            let report = downloader.fetch_available_versions().await?;
+            report.warn_missing();
+            report.warn_cached();
+            report.versions
+        };
 
         // Apply filters
-        if let Some(ref filter_str) = filter {
-            versions.retain(|v| v.version.contains(filter_str));
+        if let Some(query) = &query {
+            versions.retain(|v| query.matches(v));
         }
 
-        if lts_only {
+        if options.lts {
             versions.retain(|v| v.is_lts);
         }
 
-        if graalvm_only {
+        if options.graalvm {
             versions.retain(|v| v.vendor.to_lowercase().contains("graalvm"));
         }
 
+        if options.javafx {
+            versions.retain(|v| v.javafx_bundled);
+        }
+
         if versions.is_empty() {
             println!(
                 "{}",
@@ -132,8 +227,8 @@ impl ListCommand {
 
         println!("\n{}\n", style("Available Java Versions:").bold().green());
 
-        // Group by major version
-        versions.sort_by(|a, b| b.version.cmp(&a.version));
+        // Newest first, grouping builds of a feature release together
+        versions.sort_by(|a, b| b.cmp_version(a));
 
         for version in versions {
             let lts_badge = if version.is_lts {
@@ -142,11 +237,31 @@ impl ListCommand {
                 style("")
             };
 
+            let javafx_badge = if version.javafx_bundled {
+                style(" [JavaFX]").magenta()
+            } else {
+                style("")
+            };
+
+            let image_badge = match version.image_type.badge() {
+                Some(badge) => style(format!(" [{}]", badge)).blue(),
+                None => style(String::new()),
+            };
+
+            let ea_badge = if version.channel == Channel::Ea {
+                style(" [EA]").red()
+            } else {
+                style("")
+            };
+
             println!(
-                "  {} - {}{} - {}",
+                "  {} - {}{}{}{}{} - {}",
                 style(&version.version).white().bold(),
                 style(&version.vendor).dim(),
                 lts_badge,
+                ea_badge,
+                image_badge,
+                javafx_badge,
                 style(&version.architecture).dim()
             );
         }
@@ -0,0 +1,122 @@
+use crate::config::Config;
+use crate::project::{self, JAVA_VERSION_FILE};
+use crate::query::VersionQuery;
+use anyhow::{Context, Result};
+use console::style;
+use std::fs;
+use std::path::Path;
+
+pub struct LocalCommand;
+
+impl LocalCommand {
+    /// Pin `spec` for the current directory, remove the pin, or show which
+    /// version is in effect here
+    pub fn execute(spec: Option<String>, unset: bool) -> Result<()> {
+        let dir = std::env::current_dir().context("Failed to read the current directory")?;
+
+        if unset {
+            return Self::unset(&dir);
+        }
+
+        match spec {
+            Some(spec) => Self::pin(&dir, spec.trim()),
+            None => Self::show(&dir),
+        }
+    }
+
+    fn pin(dir: &Path, spec: &str) -> Result<()> {
+        let config = Config::load()?;
+
+        // An ID or a valid query; ambiguous queries are refused now rather
+        // than every time the file is read
+        let installed = match config.get_by_id(spec) {
+            Some(version) => Some(version),
+            None => {
+                let query: VersionQuery = spec.parse()?;
+                query.select(&config.installed_versions)?
+            }
+        };
+
+        let file = dir.join(JAVA_VERSION_FILE);
+        fs::write(&file, format!("{}\n", spec))
+            .with_context(|| format!("Failed to write {}", file.display()))?;
+
+        println!(
+            "{} Wrote {} to {}",
+            style("✓").green().bold(),
+            style(spec).cyan().bold(),
+            style(file.display()).dim()
+        );
+
+        match installed {
+            Some(version) => println!(
+                "  Java {} ({}) will be used in this directory",
+                style(&version.version).cyan(),
+                style(&version.vendor).dim()
+            ),
+            None => println!(
+                "{}",
+                style(format!(
+                    "No installed version matches {} yet; install it with 'jaman install {}'",
+                    spec, spec
+                ))
+                .yellow()
+            ),
+        }
+
+        Ok(())
+    }
+
+    fn unset(dir: &Path) -> Result<()> {
+        let file = dir.join(JAVA_VERSION_FILE);
+        if !file.exists() {
+            println!(
+                "{}",
+                style(format!("No {} in this directory", JAVA_VERSION_FILE)).yellow()
+            );
+            return Ok(());
+        }
+
+        fs::remove_file(&file).with_context(|| format!("Failed to remove {}", file.display()))?;
+        println!(
+            "{} Removed {}",
+            style("✓").green().bold(),
+            style(file.display()).dim()
+        );
+        Ok(())
+    }
+
+    fn show(dir: &Path) -> Result<()> {
+        let config = Config::load()?;
+
+        match project::effective_version(&config, dir)? {
+            Some((version, source)) => {
+                let source = match source {
+                    Some(project) => format!("set by {}", project.file.display()),
+                    None => "globally active".to_string(),
+                };
+                println!(
+                    "{} {} - {} ({})",
+                    style("Java").bold(),
+                    style(&version.version).green().bold(),
+                    style(&version.vendor).dim(),
+                    source
+                );
+                println!("  ID:   {}", style(&version.id).dim());
+                println!("  Path: {}", style(version.path.display()).dim());
+            }
+            None => {
+                println!(
+                    "{}",
+                    style("No project version file and no active version.").yellow()
+                );
+                println!(
+                    "\nUse {} to pin a version for this directory.",
+                    style("jaman local <version>").cyan()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,13 +1,21 @@
 pub mod activate;
+pub mod cache;
 pub mod clean;
 pub mod doctor;
+pub mod env;
+pub mod hook;
 pub mod install;
 pub mod list;
+pub mod local;
 pub mod scan;
 
 pub use activate::ActivateCommand;
+pub use cache::CacheCommand;
 pub use clean::CleanCommand;
 pub use doctor::DoctorCommand;
-pub use install::InstallCommand;
-pub use list::ListCommand;
+pub use env::EnvCommand;
+pub use hook::HookCommand;
+pub use install::{ArchiveSource, InstallCommand};
+pub use list::{ListCommand, ListOptions};
+pub use local::LocalCommand;
 pub use scan::ScanCommand;
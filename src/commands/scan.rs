@@ -9,6 +9,7 @@ pub struct ScanCommand;
 
 impl ScanCommand {
     pub async fn execute() -> Result<()> {
+        let _lock = Config::lock_async().await?;
         let mut config = Config::load()?;
 
         println!(
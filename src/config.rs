@@ -1,20 +1,129 @@
-use anyhow::Result;
+use crate::lock::FileLock;
+use crate::migrations::{self, SCHEMA_VERSION};
+use crate::query::VersionQuery;
+use crate::version::{self, Version};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use console::style;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// How many previous versions of config.toml are kept as backups
+pub const CONFIG_BACKUPS: usize = 3;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Layout version of the file, see `migrations`
+    #[serde(default)]
+    pub schema_version: u32,
     pub installation_dir: PathBuf,
     pub download_dir: PathBuf,
-    pub active_version: Option<String>,
+    /// ID of the active installation, see `JavaVersion::id`
+    pub active_id: Option<String>,
     pub installed_versions: Vec<JavaVersion>,
     pub last_scan: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub metadata_backend: MetadataBackend,
+    #[serde(default)]
+    pub release_selection: ReleaseSelection,
+    #[serde(default)]
+    pub network: NetworkSettings,
+    #[serde(default)]
+    pub proxy: ProxySettings,
+    /// Extra PEM bundles of root certificates to trust, e.g. a corporate CA
+    #[serde(default)]
+    pub ca_certificates: Vec<PathBuf>,
+    /// Hours a fetched version listing is reused before it is fetched again
+    #[serde(default = "default_metadata_ttl_hours")]
+    pub metadata_ttl_hours: u64,
+}
+
+fn default_metadata_ttl_hours() -> u64 {
+    24
+}
+
+/// Timeouts and retry behaviour for every HTTP request jaman makes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    /// Seconds allowed for establishing a connection
+    pub connect_timeout_secs: u64,
+    /// Seconds a metadata request, or a stalled download, may take
+    pub read_timeout_secs: u64,
+    /// How often a request failing with a transient error is retried
+    pub retries: u32,
+    /// Delay before the first retry in milliseconds, doubled on every further one
+    pub backoff_ms: u64,
+}
+
+/// HTTP(S) proxy for every request. Without a URL the `HTTPS_PROXY`,
+/// `ALL_PROXY` and `NO_PROXY` environment variables apply.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    /// Proxy URL, e.g. "http://proxy.corp.example:3128"
+    pub url: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Hosts reached directly, e.g. "localhost" or ".corp.example";
+    /// defaults to `NO_PROXY`
+    pub no_proxy: Vec<String>,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: 10,
+            read_timeout_secs: 30,
+            retries: 3,
+            backoff_ms: 500,
+        }
+    }
+}
+
+/// Where `Downloader` gets its list of available versions from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataBackend {
+    /// Query each vendor's own API
+    #[default]
+    Direct,
+    /// Query the foojay Discovery API, which aggregates many vendors
+    Foojay,
+}
+
+impl std::str::FromStr for MetadataBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "direct" => Ok(Self::Direct),
+            "foojay" => Ok(Self::Foojay),
+            _ => Err(anyhow::anyhow!(
+                "Unknown metadata backend '{}' (expected 'direct' or 'foojay')",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for MetadataBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Direct => write!(f, "direct"),
+            Self::Foojay => write!(f, "foojay"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JavaVersion {
+    /// Stable unique ID, e.g. "temurin-21.0.2+13-x64", assigned by
+    /// `Config::add_version` and used to refer to this installation
+    pub id: String,
     pub version: String,
     pub vendor: String,
     pub path: PathBuf,
@@ -22,16 +131,216 @@ pub struct JavaVersion {
     pub architecture: String,
     pub last_used: Option<DateTime<Utc>>,
     pub auto_detected: bool,
+    #[serde(default)]
+    pub javafx_bundled: bool,
+    /// Whether the JDK ships GraalVM's `native-image` tool
+    #[serde(default)]
+    pub native_image: bool,
+    #[serde(default)]
+    pub image_type: ImageType,
+    #[serde(default)]
+    pub channel: Channel,
+}
+
+/// Kind of Java image a package contains
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageType {
+    /// Full development kit
+    #[default]
+    Jdk,
+    /// Runtime only, without compiler and tools
+    Jre,
+    /// JDK without GUI libraries (AWT/Swing), for servers and containers
+    Headless,
+    /// JDK debug symbols image
+    Debug,
+}
+
+impl ImageType {
+    /// Short label for listings, `None` for the default JDK image
+    pub fn badge(&self) -> Option<&'static str> {
+        match self {
+            Self::Jdk => None,
+            Self::Jre => Some("JRE"),
+            Self::Headless => Some("headless"),
+            Self::Debug => Some("debug"),
+        }
+    }
+}
+
+impl std::str::FromStr for ImageType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "jdk" => Ok(Self::Jdk),
+            "jre" => Ok(Self::Jre),
+            "headless" => Ok(Self::Headless),
+            "debug" => Ok(Self::Debug),
+            _ => Err(anyhow::anyhow!(
+                "Unknown image type '{}' (expected 'jdk', 'jre', 'headless' or 'debug')",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ImageType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Jdk => write!(f, "jdk"),
+            Self::Jre => write!(f, "jre"),
+            Self::Headless => write!(f, "headless"),
+            Self::Debug => write!(f, "debug"),
+        }
+    }
+}
+
+/// Release channel a build was published on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    /// General availability releases
+    #[default]
+    Ga,
+    /// Early-access builds of upcoming feature releases
+    Ea,
+}
+
+impl std::str::FromStr for Channel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "ga" => Ok(Self::Ga),
+            "ea" => Ok(Self::Ea),
+            _ => Err(anyhow::anyhow!(
+                "Unknown release channel '{}' (expected 'ga' or 'ea')",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ga => write!(f, "ga"),
+            Self::Ea => write!(f, "ea"),
+        }
+    }
+}
+
+/// Which feature releases `Downloader` fetches assets for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ReleaseSelection {
+    /// Only long-term support releases
+    AllLts,
+    /// Every GA release a provider offers
+    #[default]
+    AllGa,
+    /// Feature releases within an inclusive range; either bound may be open
+    Range { min: Option<u32>, max: Option<u32> },
+}
+
+impl ReleaseSelection {
+    pub fn includes(&self, major: u32, is_lts: bool) -> bool {
+        match self {
+            Self::AllLts => is_lts,
+            Self::AllGa => true,
+            Self::Range { min, max } => {
+                min.is_none_or(|min| major >= min) && max.is_none_or(|max| major <= max)
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ReleaseSelection {
+    type Err = anyhow::Error;
+
+    /// Parse "lts", "ga", "17..21", "17..", "..21" or a single "21"
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid release selection '{}' (expected 'lts', 'ga' or a range like '17..21')",
+                s
+            )
+        };
+        let parse_bound = |bound: &str| -> Result<Option<u32>> {
+            if bound.is_empty() {
+                Ok(None)
+            } else {
+                bound.parse().map(Some).map_err(|_| invalid())
+            }
+        };
+
+        match s.trim().to_lowercase().as_str() {
+            "lts" => Ok(Self::AllLts),
+            "ga" | "all" => Ok(Self::AllGa),
+            range => {
+                let (min, max) = match range.split_once("..") {
+                    Some((min, max)) => (parse_bound(min)?, parse_bound(max)?),
+                    None => {
+                        let major = parse_bound(range)?.ok_or_else(invalid)?;
+                        (Some(major), Some(major))
+                    }
+                };
+                Ok(Self::Range { min, max })
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ReleaseSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AllLts => write!(f, "lts"),
+            Self::AllGa => write!(f, "ga"),
+            Self::Range { min, max } => {
+                if let Some(min) = min {
+                    write!(f, "{}", min)?;
+                }
+                write!(f, "..")?;
+                if let Some(max) = max {
+                    write!(f, "{}", max)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for ReleaseSelection {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<ReleaseSelection> for String {
+    fn from(selection: ReleaseSelection) -> Self {
+        selection.to_string()
+    }
 }
 
 impl Config {
     pub fn new(installation_dir: PathBuf, download_dir: PathBuf) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             installation_dir,
             download_dir,
-            active_version: None,
+            active_id: None,
             installed_versions: Vec::new(),
             last_scan: None,
+            metadata_backend: MetadataBackend::default(),
+            release_selection: ReleaseSelection::default(),
+            network: NetworkSettings::default(),
+            proxy: ProxySettings::default(),
+            ca_certificates: Vec::new(),
+            metadata_ttl_hours: default_metadata_ttl_hours(),
         }
     }
 
@@ -58,15 +367,144 @@ impl Config {
             return Self::default_config();
         }
 
-        let content = fs::read_to_string(config_file)?;
-        let config: Config = toml::from_str(&content)?;
+        match Self::load_from(&config_file) {
+            Ok(config) => Ok(config),
+            Err(e) => Self::recover(&config_file, e),
+        }
+    }
+
+    /// Read and parse the config file at `path`
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::from_toml(&content)
+            .with_context(|| format!("{} is not a valid config", path.display()))
+    }
+
+    /// Parse a config, upgrading it first if an older jaman wrote it. The
+    /// upgraded layout is written back on the next `save`.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        migrations::migrate(&mut table)?;
+        Ok(toml::Value::Table(table).try_into()?)
+    }
+
+    /// Offer to restore the newest valid backup after `config_file` failed to
+    /// load. Without a terminal to ask on, the error names the backup instead.
+    fn recover(config_file: &Path, error: anyhow::Error) -> Result<Self> {
+        let Some((backup, _)) = Self::latest_valid_backup(config_file) else {
+            return Err(error);
+        };
+
+        let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+        if !interactive {
+            return Err(error.context(format!(
+                "A valid backup is available at {}; copy it over {} to restore it",
+                backup.display(),
+                config_file.display()
+            )));
+        }
+
+        println!("{} {:#}", style("Warning:").yellow().bold(), error);
+        let restore = dialoguer::Confirm::new()
+            .with_prompt(format!("Restore the config from {}?", backup.display()))
+            .default(true)
+            .interact()?;
+        if !restore {
+            return Err(error);
+        }
+
+        let (_, config) = Self::restore_backup(config_file)?
+            .ok_or_else(|| anyhow::anyhow!("No valid config backup found"))?;
+        println!(
+            "{}",
+            style(format!(
+                "Config restored; the damaged file was kept as {}",
+                Self::damaged_path(config_file).display()
+            ))
+            .green()
+        );
         Ok(config)
     }
 
+    /// Backup `n` (1 is the newest) of the config file at `config_file`
+    pub fn backup_path(config_file: &Path, n: usize) -> PathBuf {
+        let mut name = config_file.as_os_str().to_owned();
+        name.push(format!(".bak.{}", n));
+        PathBuf::from(name)
+    }
+
+    fn damaged_path(config_file: &Path) -> PathBuf {
+        let mut name = config_file.as_os_str().to_owned();
+        name.push(".damaged");
+        PathBuf::from(name)
+    }
+
+    /// The newest backup of `config_file` that still parses
+    pub fn latest_valid_backup(config_file: &Path) -> Option<(PathBuf, Self)> {
+        (1..=CONFIG_BACKUPS)
+            .map(|n| Self::backup_path(config_file, n))
+            .find_map(|backup| Self::load_from(&backup).ok().map(|config| (backup, config)))
+    }
+
+    /// Replace `config_file` with its newest valid backup, keeping the damaged
+    /// file next to it. Returns the backup used and the restored config.
+    pub fn restore_backup(config_file: &Path) -> Result<Option<(PathBuf, Self)>> {
+        let Some((backup, config)) = Self::latest_valid_backup(config_file) else {
+            return Ok(None);
+        };
+
+        if config_file.exists() {
+            fs::rename(config_file, Self::damaged_path(config_file))?;
+        }
+        fs::copy(&backup, config_file)?;
+
+        Ok(Some((backup, config)))
+    }
+
+    /// Lock the config against other jaman processes. Hold the lock from
+    /// `load` through `save` so concurrent changes are not overwritten.
+    pub fn lock() -> Result<FileLock> {
+        FileLock::exclusive(&Self::config_dir()?.join("config.lock"), "the jaman config")
+    }
+
+    /// `lock` for async code, waiting without blocking the runtime
+    pub async fn lock_async() -> Result<FileLock> {
+        FileLock::exclusive_async(&Self::config_dir()?.join("config.lock"), "the jaman config")
+            .await
+    }
+
     pub fn save(&self) -> Result<()> {
-        let config_file = Self::config_file()?;
+        self.save_to(&Self::config_file()?)
+    }
+
+    /// Write the config to `path` atomically: the new content goes to a
+    /// temporary file that replaces `path` only once fully written, and the
+    /// previous content is kept in up to `CONFIG_BACKUPS` backups
+    pub fn save_to(&self, path: &Path) -> Result<()> {
         let content = toml::to_string_pretty(self)?;
-        fs::write(config_file, content)?;
+        // Unchanged configs would only push older backups out
+        if fs::read_to_string(path).is_ok_and(|current| current == content) {
+            return Ok(());
+        }
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+        temp.write_all(content.as_bytes())?;
+        temp.as_file().sync_all()?;
+
+        // Only a config that still loads is worth keeping as a backup
+        if Self::load_from(path).is_ok() {
+            for n in (1..CONFIG_BACKUPS).rev() {
+                let older = Self::backup_path(path, n);
+                if older.exists() {
+                    fs::rename(&older, Self::backup_path(path, n + 1))?;
+                }
+            }
+            fs::copy(path, Self::backup_path(path, 1))?;
+        }
+
+        temp.persist(path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
         Ok(())
     }
 
@@ -92,28 +530,99 @@ impl Config {
         Ok(Self::new(installation_dir, download_dir))
     }
 
-    pub fn add_version(&mut self, version: JavaVersion) {
-        // Remove if exists
+    /// Track an installation and return its ID. Re-adding a path (e.g. on a
+    /// rescan) replaces the entry but keeps the ID it was given first.
+    pub fn add_version(&mut self, mut version: JavaVersion) -> String {
+        let previous_id = self
+            .installed_versions
+            .iter()
+            .find(|v| v.path == version.path)
+            .map(|v| v.id.clone());
         self.installed_versions.retain(|v| v.path != version.path);
+
+        version.id = match previous_id {
+            Some(id) => id,
+            None => unique_id(
+                version.default_id(),
+                self.installed_versions.iter().map(|v| v.id.as_str()),
+            ),
+        };
+        let id = version.id.clone();
         self.installed_versions.push(version);
+        id
     }
 
-    pub fn remove_version(&mut self, path: &PathBuf) {
-        self.installed_versions.retain(|v| &v.path != path);
+    pub fn remove_version(&mut self, id: &str) {
+        self.installed_versions.retain(|v| v.id != id);
+        if self.active_id.as_deref() == Some(id) {
+            self.active_id = None;
+        }
     }
 
-    pub fn get_version(&self, version_str: &str) -> Option<&JavaVersion> {
+    /// The active installation, if one is set and still tracked
+    pub fn active(&self) -> Option<&JavaVersion> {
+        let id = self.active_id.as_ref()?;
+        self.installed_versions.iter().find(|v| &v.id == id)
+    }
+
+    /// The installation with the given ID, e.g. "temurin-21.0.2+13-x64"
+    pub fn get_by_id(&self, id: &str) -> Option<&JavaVersion> {
+        self.installed_versions.iter().find(|v| v.id == id)
+    }
+
+    /// The installation an ID or version query such as "17", ">=17 <21" or
+    /// "temurin@21" refers to; for queries, the newest match. Fails when the
+    /// query is invalid, matches nothing, or matches several installs of the
+    /// same version.
+    pub fn get_version(&self, query: &str) -> Result<&JavaVersion> {
+        if let Some(version) = self.get_by_id(query) {
+            return Ok(version);
+        }
+
+        let query: VersionQuery = query.parse()?;
+        query
+            .select(&self.installed_versions)?
+            .ok_or_else(|| anyhow::anyhow!("Version {} not found", query))
+    }
+
+    /// Managed early-access installs that a GA build of the same feature
+    /// release, or a newer EA build from the same vendor, has made obsolete
+    pub fn superseded_early_access(&self) -> Vec<&JavaVersion> {
         self.installed_versions
             .iter()
-            .find(|v| v.version.contains(version_str))
+            .filter(|v| v.channel == Channel::Ea && !v.auto_detected)
+            .filter(|v| self.active_id.as_ref() != Some(&v.id))
+            .filter(|ea| {
+                let Some(ea_version) = ea.parsed_version() else {
+                    return false;
+                };
+                self.installed_versions.iter().any(|other| {
+                    let Some(other_version) = other.parsed_version() else {
+                        return false;
+                    };
+                    if other_version.feature() != ea_version.feature() {
+                        return false;
+                    }
+                    match other.channel {
+                        Channel::Ga => true,
+                        Channel::Ea => {
+                            other.vendor == ea.vendor
+                                && other.image_type == ea.image_type
+                                && other_version > ea_version
+                        }
+                    }
+                })
+            })
+            .collect()
     }
 
-    pub fn set_active(&mut self, version: &str) -> Result<()> {
-        if self.get_version(version).is_some() {
-            self.active_version = Some(version.to_string());
+    /// Make the installation with the given ID the active one
+    pub fn set_active(&mut self, id: &str) -> Result<()> {
+        if self.get_by_id(id).is_some() {
+            self.active_id = Some(id.to_string());
             Ok(())
         } else {
-            Err(anyhow::anyhow!("Version {} not found", version))
+            Err(anyhow::anyhow!("Installation {} not found", id))
         }
     }
 }
@@ -128,6 +637,7 @@ impl JavaVersion {
         auto_detected: bool,
     ) -> Self {
         Self {
+            id: String::new(),
             version,
             vendor,
             path,
@@ -135,6 +645,10 @@ impl JavaVersion {
             architecture,
             last_used: None,
             auto_detected,
+            javafx_bundled: false,
+            native_image: false,
+            image_type: ImageType::default(),
+            channel: Channel::default(),
         }
     }
 
@@ -155,7 +669,89 @@ impl JavaVersion {
         }
     }
 
+    /// ID derived from vendor, version, architecture and image, before
+    /// `Config::add_version` makes it unique
+    pub fn default_id(&self) -> String {
+        installation_id(
+            &self.vendor,
+            &self.version,
+            &self.architecture,
+            self.image_type,
+            self.javafx_bundled,
+        )
+    }
+
     pub fn mark_used(&mut self) {
         self.last_used = Some(Utc::now());
     }
+
+    /// The version string parsed as a JEP 223 version, if it is one
+    pub fn parsed_version(&self) -> Option<Version> {
+        self.version.parse().ok()
+    }
+
+    /// Order by version number rather than by string, e.g. "17.0.10" after "17.0.9"
+    pub fn cmp_version(&self, other: &Self) -> Ordering {
+        version::compare(&self.version, &other.version)
+    }
+}
+
+/// Readable installation ID such as "temurin-21.0.2+13-x64", with the image
+/// type and JavaFX appended when they differ from a plain JDK:
+/// "zulu-21.0.2+13-x64-jre-fx"
+pub fn installation_id(
+    vendor: &str,
+    version: &str,
+    architecture: &str,
+    image_type: ImageType,
+    javafx_bundled: bool,
+) -> String {
+    let vendor = match vendor {
+        "Eclipse Temurin" => "temurin",
+        "Amazon Corretto" => "corretto",
+        "Azul Zulu" => "zulu",
+        "BellSoft Liberica" => "liberica",
+        "IBM Semeru" => "semeru",
+        "Alibaba Dragonwell" => "dragonwell",
+        "GraalVM CE" => "graalvm-ce",
+        "Oracle GraalVM" => "graalvm",
+        other => other,
+    };
+
+    let mut parts = vec![slug(vendor), slug(version), slug(architecture)];
+    if image_type != ImageType::Jdk {
+        parts.push(image_type.to_string());
+    }
+    if javafx_bundled {
+        parts.push("fx".to_string());
+    }
+    parts.retain(|part| !part.is_empty());
+    parts.join("-")
+}
+
+/// `base`, or `base` with the first free "-2", "-3", ... suffix when another
+/// installation already uses it
+pub fn unique_id<'a>(base: String, taken: impl Iterator<Item = &'a str> + Clone) -> String {
+    let is_taken = |id: &str| taken.clone().any(|t| t == id);
+    if !is_taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|id| !is_taken(id))
+        .expect("an unused suffix exists")
+}
+
+/// Lowercase `text`, keeping the characters of version strings and replacing
+/// anything else (spaces, slashes) with single dashes
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '_') {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
 }
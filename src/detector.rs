@@ -1,6 +1,9 @@
-use crate::config::JavaVersion;
+use crate::config::{ImageType, JavaVersion};
+use crate::downloader::STAGING_DIR;
 use anyhow::Result;
 use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
@@ -185,6 +188,8 @@ impl JavaDetector {
             .max_depth(3)
             .follow_links(true)
             .into_iter()
+            // Unfinished installs are not installations
+            .filter_entry(|e| e.file_name() != STAGING_DIR)
             .filter_map(|e| e.ok())
         {
             let entry_path = entry.path();
@@ -233,14 +238,18 @@ impl JavaDetector {
         let is_lts = Self::is_lts_version(&version);
         let architecture = Self::detect_architecture(&version_output);
 
-        Ok(JavaVersion::new(
+        let mut java_version = JavaVersion::new(
             version,
             vendor,
             path.to_path_buf(),
             is_lts,
             architecture,
             true, // auto_detected = true
-        ))
+        );
+        java_version.native_image = Self::has_native_image(path);
+        java_version.image_type = Self::detect_image_type(path);
+
+        Ok(java_version)
     }
 
     fn parse_version_output(output: &str) -> Result<(String, String)> {
@@ -300,6 +309,102 @@ impl JavaDetector {
         }
     }
 
+    /// Identify an unpacked JDK, preferring its `release` file over running
+    /// `java -version` so archives for other platforms can be inspected too
+    pub fn inspect(path: &Path) -> Result<JavaVersion> {
+        let Some(release) = Self::read_release_file(path) else {
+            let mut java_version = Self::detect_version(path)?;
+            java_version.auto_detected = false;
+            return Ok(java_version);
+        };
+
+        let version = release
+            .get("JAVA_VERSION")
+            .ok_or_else(|| anyhow::anyhow!("No JAVA_VERSION in {}", path.display()))?;
+        let version = match version.strip_prefix("1.") {
+            Some(legacy) => legacy.to_string(),
+            None => version.clone(),
+        };
+        let vendor = release
+            .get("IMPLEMENTOR")
+            .map(|implementor| Self::vendor_name(implementor))
+            .unwrap_or_else(|| "Unknown".to_string());
+        let architecture = release
+            .get("OS_ARCH")
+            .map(|arch| Self::detect_architecture(arch))
+            .unwrap_or_else(|| std::env::consts::ARCH.to_string());
+
+        let mut java_version = JavaVersion::new(
+            version.clone(),
+            vendor,
+            path.to_path_buf(),
+            Self::is_lts_version(&version),
+            architecture,
+            false,
+        );
+        java_version.native_image = Self::has_native_image(path);
+        java_version.image_type = Self::detect_image_type(path);
+
+        Ok(java_version)
+    }
+
+    /// Key/value pairs of a JDK's `release` file, e.g. `JAVA_VERSION="21.0.2"`
+    fn read_release_file(path: &Path) -> Option<HashMap<String, String>> {
+        let content = fs::read_to_string(path.join("release")).ok()?;
+
+        Some(
+            content
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(key, value)| {
+                    (
+                        key.trim().to_string(),
+                        value.trim().trim_matches('"').to_string(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Map a `release` file IMPLEMENTOR to the vendor names providers use
+    fn vendor_name(implementor: &str) -> String {
+        match implementor {
+            "Eclipse Adoptium" => "Eclipse Temurin",
+            "Amazon.com Inc." => "Amazon Corretto",
+            "Azul Systems, Inc." => "Azul Zulu",
+            "BellSoft" => "BellSoft Liberica",
+            "GraalVM Community" => "GraalVM CE",
+            other => other,
+        }
+        .to_string()
+    }
+
+    /// Tell a JDK from a JRE by the presence of the `javac` compiler
+    pub fn detect_image_type(path: &Path) -> ImageType {
+        let javac = if cfg!(windows) {
+            path.join("bin").join("javac.exe")
+        } else {
+            path.join("bin").join("javac")
+        };
+
+        if javac.exists() {
+            ImageType::Jdk
+        } else {
+            ImageType::Jre
+        }
+    }
+
+    /// Check whether a JDK ships GraalVM's `native-image` tool
+    pub fn has_native_image(path: &Path) -> bool {
+        let bin_dir = path.join("bin");
+
+        if cfg!(windows) {
+            bin_dir.join("native-image.cmd").exists() || bin_dir.join("native-image.exe").exists()
+        } else {
+            bin_dir.join("native-image").exists()
+        }
+    }
+
     /// Verify a Java installation is valid
     pub fn verify_installation(path: &Path) -> Result<bool> {
         let java_exe = if cfg!(windows) {
@@ -1,13 +1,40 @@
-use anyhow::Result;
-use futures_util::StreamExt;
+use crate::cache::{ArchiveCache, CacheMode, CachedMetadata, MetadataCache};
+use crate::config::{
+    Channel, Config, ImageType, JavaVersion, MetadataBackend, NetworkSettings, ProxySettings,
+    ReleaseSelection,
+};
+use crate::detector::JavaDetector;
+use crate::extract;
+use crate::lock::FileLock;
+use crate::providers::{
+    CorrettoProvider, FoojayProvider, GraalVmCeProvider, OracleGraalVmProvider, Provider, Target,
+    TemurinProvider, ZuluProvider,
+};
+use crate::retry::{ReadTimeout, RetryPolicy};
+use crate::version;
+use anyhow::{Context, Result};
+use futures_util::{StreamExt, future, stream};
 use indicatif::{ProgressBar, ProgressStyle};
-use reqwest::Client;
+use reqwest::{Certificate, Client, NoProxy, Proxy};
 use serde::{Deserialize, Serialize};
-use std::fs::{self, File};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::fs::{self, File, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tempfile::TempDir;
 
+/// Directory inside the installation dir where archives are unpacked before
+/// they are moved into place
+pub const STAGING_DIR: &str = ".staging";
+
+/// Directory inside the installation dir holding per-install lock files
+pub const LOCK_DIR: &str = ".locks";
+
+/// Upper bound on concurrent metadata requests per provider
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AvailableVersion {
     pub version: String,
@@ -16,10 +43,146 @@ pub struct AvailableVersion {
     pub architecture: String,
     pub download_url: String,
     pub checksum: Option<String>,
+    /// Whether the package bundles JavaFX (e.g. Zulu "fx" builds)
+    #[serde(default)]
+    pub javafx_bundled: bool,
+    #[serde(default)]
+    pub image_type: ImageType,
+    #[serde(default)]
+    pub channel: Channel,
+}
+
+impl AvailableVersion {
+    /// Order by version number rather than by string, e.g. "17.0.10" after "17.0.9"
+    pub fn cmp_version(&self, other: &Self) -> Ordering {
+        version::compare(&self.version, &other.version)
+    }
+
+    /// Whether the listing names only the feature release ("21"), as Oracle's
+    /// "latest" links do. The exact version is then read from the JDK.
+    pub fn is_feature_only(&self) -> bool {
+        self.version.chars().all(|c| c.is_ascii_digit())
+    }
+
+    /// Whether `installed` is this build. A feature-only listing matches any
+    /// install of its feature release, as its exact version is only known
+    /// once the JDK is unpacked.
+    pub fn is_installed_as(&self, installed: &JavaVersion) -> bool {
+        let same_version = if self.is_feature_only() {
+            installed
+                .version
+                .parse::<version::Version>()
+                .is_ok_and(|v| v.feature().to_string() == self.version)
+        } else {
+            installed.version == self.version
+        };
+        same_version
+            && installed.vendor == self.vendor
+            && installed.javafx_bundled == self.javafx_bundled
+            && installed.image_type == self.image_type
+            && installed.channel == self.channel
+    }
+
+    /// One build per vendor and version. JavaFX builds share their version
+    /// with the vendor's plain build, which is the one a query means unless
+    /// `javafx` asks for JavaFX builds only.
+    pub fn distinct_builds(versions: &[Self], javafx: bool) -> Vec<&Self> {
+        let mut builds: Vec<&Self> = Vec::new();
+        for version in versions.iter().filter(|v| v.javafx_bundled || !javafx) {
+            match builds
+                .iter_mut()
+                .find(|build| build.vendor == version.vendor && build.version == version.version)
+            {
+                Some(build) if build.javafx_bundled && !version.javafx_bundled => *build = version,
+                Some(_) => {}
+                None => builds.push(version),
+            }
+        }
+        builds
+    }
+}
+
+/// Versions fetched from the providers, plus the releases that could not be
+/// fetched even after retrying
+#[derive(Debug, Default)]
+pub struct FetchReport {
+    pub versions: Vec<AvailableVersion>,
+    pub missing: Vec<MissingRelease>,
+    /// When the listing was fetched, if it came from the metadata cache
+    pub cached_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A release, or a whole provider's listing, left out of a `FetchReport`
+#[derive(Debug, Clone)]
+pub struct MissingRelease {
+    pub vendor: String,
+    /// `None` when the provider's release list itself could not be fetched
+    pub major: Option<u32>,
+    pub error: String,
+}
+
+impl std::fmt::Display for MissingRelease {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.major {
+            Some(major) => write!(f, "{} {}: {}", self.vendor, major, self.error),
+            None => write!(f, "{} (all releases): {}", self.vendor, self.error),
+        }
+    }
+}
+
+impl FetchReport {
+    /// Print a warning for every release that is missing from the listing
+    pub fn warn_missing(&self) {
+        if self.missing.is_empty() {
+            return;
+        }
+
+        println!(
+            "{}",
+            console::style("Some releases could not be fetched and are not listed:").yellow()
+        );
+        for missing in &self.missing {
+            println!("  {} {}", console::style("⚠").yellow(), missing);
+        }
+    }
+
+    /// Note when the listing came from the metadata cache rather than the network
+    pub fn warn_cached(&self) {
+        if let Some(fetched_at) = self.cached_at {
+            println!(
+                "{}",
+                console::style(format!(
+                    "Using version list cached at {} (--refresh to fetch it again)",
+                    fetched_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                ))
+                .dim()
+            );
+        }
+    }
 }
 
 pub struct Downloader {
+    /// Client for metadata requests, bounded by the read timeout
     client: Client,
+    /// Client for archive downloads, which may take longer than the read timeout
+    download_client: Client,
+    network: NetworkSettings,
+    retry: RetryPolicy,
+    proxy: Option<Proxy>,
+    /// Root certificates trusted in addition to the system store
+    certificates: Vec<Certificate>,
+    providers: Vec<Box<dyn Provider>>,
+    target: Target,
+    selection: ReleaseSelection,
+    channel: Channel,
+    /// Where archives are kept between installs; without one they are
+    /// downloaded into a temporary directory
+    cache: Option<ArchiveCache>,
+    /// Where fetched listings are kept; without one every listing is fetched
+    metadata: Option<MetadataCache>,
+    cache_mode: CacheMode,
 }
 
 impl Default for Downloader {
@@ -30,69 +193,417 @@ impl Default for Downloader {
 
 impl Downloader {
     pub fn new() -> Self {
-        Self {
-            client: Client::builder().user_agent("jaman/0.1.0").build().unwrap(),
-        }
+        Self::with_providers(Self::direct_providers())
     }
 
-    /// Fetch available Java versions from Adoptium API
-    pub async fn fetch_available_versions(&self) -> Result<Vec<AvailableVersion>> {
-        let mut versions = Vec::new();
+    /// Create a downloader using the metadata backend, network, proxy and CA
+    /// settings in `config`
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let downloader = match config.metadata_backend {
+            MetadataBackend::Direct => Self::new(),
+            MetadataBackend::Foojay => Self::with_providers(vec![Box::new(FoojayProvider::new())]),
+        };
 
-        // Fetch from Adoptium (Eclipse Temurin)
-        versions.extend(self.fetch_adoptium_versions().await?);
+        downloader
+            .with_selection(config.release_selection)
+            .with_cache_dir(&config.download_dir)
+            .with_metadata_cache(
+                Config::config_dir()?.join("metadata"),
+                chrono::Duration::hours(config.metadata_ttl_hours as i64),
+            )
+            .with_network(config.network)
+            .with_proxy(&config.proxy)?
+            .with_ca_certificates(&config.ca_certificates)
+    }
 
-        Ok(versions)
+    /// One provider per vendor, each talking to the vendor's own API
+    fn direct_providers() -> Vec<Box<dyn Provider>> {
+        vec![
+            Box::new(TemurinProvider::new()),
+            Box::new(CorrettoProvider::new()),
+            Box::new(ZuluProvider::new()),
+            Box::new(GraalVmCeProvider::new()),
+            Box::new(OracleGraalVmProvider::new()),
+        ]
     }
 
-    async fn fetch_adoptium_versions(&self) -> Result<Vec<AvailableVersion>> {
-        let mut versions = Vec::new();
-        let base_url = "https://api.adoptium.net/v3/info/available_releases";
+    /// Create a downloader backed by the given providers
+    pub fn with_providers(providers: Vec<Box<dyn Provider>>) -> Self {
+        let network = NetworkSettings::default();
+        let mut downloader = Self {
+            client: Client::new(),
+            download_client: Client::new(),
+            network,
+            retry: RetryPolicy::from_settings(&network),
+            proxy: None,
+            certificates: Vec::new(),
+            providers,
+            target: Target::host(),
+            selection: ReleaseSelection::default(),
+            channel: Channel::default(),
+            cache: None,
+            metadata: None,
+            cache_mode: CacheMode::default(),
+        };
+        downloader.rebuild_clients();
+        downloader
+    }
+
+    /// Rebuild both HTTP clients after the network, proxy or CA settings changed
+    fn rebuild_clients(&mut self) {
+        self.client = self.build_client(true);
+        self.download_client = self.build_client(false);
+    }
+
+    fn build_client(&self, bounded: bool) -> Client {
+        let mut builder = Client::builder()
+            .user_agent("jaman/0.1.0")
+            .connect_timeout(Duration::from_secs(self.network.connect_timeout_secs));
+        if bounded {
+            builder = builder.timeout(Duration::from_secs(self.network.read_timeout_secs));
+        }
+        // Without an explicit proxy reqwest follows HTTPS_PROXY/NO_PROXY itself
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        for certificate in &self.certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        builder.build().unwrap()
+    }
 
-        let available_info: AdoptiumAvailableReleases =
-            self.client.get(base_url).send().await?.json().await?;
+    /// Apply timeouts and the retry policy from `network`
+    pub fn with_network(mut self, network: NetworkSettings) -> Self {
+        self.retry = RetryPolicy::from_settings(&network);
+        self.network = network;
+        self.rebuild_clients();
+        self
+    }
 
-        let os = if cfg!(windows) {
-            "windows"
-        } else if cfg!(target_os = "macos") {
-            "mac"
-        } else {
-            "linux"
+    /// Send every request through the proxy in `settings`. Credentials without
+    /// a URL apply to the proxy named by `HTTPS_PROXY` or `ALL_PROXY`.
+    pub fn with_proxy(mut self, settings: &ProxySettings) -> Result<Self> {
+        let env_url = || {
+            ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|url| !url.is_empty()))
+        };
+        let url = match (&settings.url, &settings.username) {
+            (Some(url), _) => url.clone(),
+            (None, Some(_)) => match env_url() {
+                Some(url) => url,
+                None => anyhow::bail!("Proxy credentials are set, but no proxy URL"),
+            },
+            // Nothing configured: leave the environment to reqwest
+            (None, None) => return Ok(self),
         };
 
-        let arch = match std::env::consts::ARCH {
-            "x86_64" => "x64",
-            "aarch64" => "aarch64",
-            _ => "x64",
+        let mut proxy = Proxy::all(&url).with_context(|| format!("Invalid proxy URL '{}'", url))?;
+        if let Some(username) = &settings.username {
+            proxy = proxy.basic_auth(username, settings.password.as_deref().unwrap_or(""));
+        }
+        let no_proxy = if settings.no_proxy.is_empty() {
+            NoProxy::from_env()
+        } else {
+            NoProxy::from_string(&settings.no_proxy.join(","))
         };
 
-        // Fetch details for each version
-        for version in available_info.available_releases.iter().take(10) {
-            let url = format!(
-                "https://api.adoptium.net/v3/assets/latest/{}/hotspot?architecture={}&image_type=jdk&os={}",
-                version, arch, os
-            );
+        self.proxy = Some(proxy.no_proxy(no_proxy));
+        self.rebuild_clients();
+        Ok(self)
+    }
+
+    /// Trust the root certificates in each PEM bundle on top of the system ones
+    pub fn with_ca_certificates(mut self, bundles: &[PathBuf]) -> Result<Self> {
+        for bundle in bundles {
+            let pem = fs::read(bundle)
+                .with_context(|| format!("Failed to read CA bundle {}", bundle.display()))?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("Invalid CA bundle {}", bundle.display()))?;
+            if certificates.is_empty() {
+                anyhow::bail!("No certificates found in CA bundle {}", bundle.display());
+            }
+            self.certificates.extend(certificates);
+        }
+
+        self.rebuild_clients();
+        Ok(self)
+    }
+
+    /// Override the retry policy, e.g. to fail fast
+    #[allow(dead_code)]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Add a provider to the registry
+    #[allow(dead_code)]
+    pub fn register(&mut self, provider: Box<dyn Provider>) {
+        self.providers.push(provider);
+    }
+
+    /// Resolve assets for another OS/architecture than the host
+    #[allow(dead_code)]
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Resolve another image type than the full JDK
+    pub fn with_image_type(mut self, image_type: ImageType) -> Self {
+        self.target.image_type = image_type;
+        self
+    }
+
+    /// Only fetch assets for the feature releases `selection` includes
+    pub fn with_selection(mut self, selection: ReleaseSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Keep downloaded archives in `dir`, resuming and reusing them
+    pub fn with_cache_dir(mut self, dir: &Path) -> Self {
+        self.cache = Some(ArchiveCache::new(dir));
+        self
+    }
+
+    /// Keep fetched listings in `dir`, reusing them for `ttl`
+    pub fn with_metadata_cache(mut self, dir: impl Into<PathBuf>, ttl: chrono::Duration) -> Self {
+        self.metadata = Some(MetadataCache::new(dir, ttl));
+        self
+    }
+
+    /// Force fresh listings, or work only from cached listings and archives
+    pub fn with_cache_mode(mut self, mode: CacheMode) -> Self {
+        self.cache_mode = mode;
+        self
+    }
+
+    /// List early-access builds instead of GA releases
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Fetch available Java versions from every registered provider. Releases
+    /// that still fail after retrying are reported in `FetchReport::missing`;
+    /// only when no provider answers at all is this an error. Complete
+    /// listings are cached and reused until they are older than the TTL.
+    pub async fn fetch_available_versions(&self) -> Result<FetchReport> {
+        let key = self.metadata_key("available");
+        if let Some(cached) = self.cached_metadata(&key)? {
+            return Ok(FetchReport {
+                versions: cached.versions,
+                missing: Vec::new(),
+                cached_at: Some(cached.fetched_at),
+            });
+        }
+
+        let report = self.fetch_from_providers().await?;
+        // A listing with gaps would hide those releases until the TTL runs out
+        if report.missing.is_empty() {
+            self.store_metadata(&key, &report.versions)?;
+        }
+        Ok(report)
+    }
+
+    async fn fetch_from_providers(&self) -> Result<FetchReport> {
+        let per_provider = future::join_all(self.providers.iter().map(|provider| async {
+            match self.channel {
+                Channel::Ga => self.fetch_provider_versions(provider.as_ref()).await,
+                Channel::Ea => self
+                    .retry
+                    .run(|| provider.list_early_access(&self.client, &self.target))
+                    .await
+                    .with_context(|| {
+                        format!("Failed to list {} early-access builds", provider.vendor())
+                    })
+                    .map(|versions| (versions, Vec::new())),
+            }
+        }))
+        .await;
+
+        let mut report = FetchReport::default();
+        let mut first_error = None;
+        let mut failed_providers = 0;
+
+        for (provider, result) in self.providers.iter().zip(per_provider) {
+            match result {
+                Ok((versions, missing)) => {
+                    report.versions.extend(versions);
+                    report.missing.extend(missing);
+                }
+                Err(e) => {
+                    failed_providers += 1;
+                    report.missing.push(MissingRelease {
+                        vendor: provider.vendor().to_string(),
+                        major: None,
+                        error: format!("{:#}", e),
+                    });
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if failed_providers == self.providers.len() => Err(e),
+            _ => Ok(report),
+        }
+    }
+
+    async fn fetch_provider_versions(
+        &self,
+        provider: &dyn Provider,
+    ) -> Result<(Vec<AvailableVersion>, Vec<MissingRelease>)> {
+        let releases = self
+            .retry
+            .run(|| provider.list_releases(&self.client))
+            .await
+            .with_context(|| format!("Failed to list {} releases", provider.vendor()))?;
+
+        // Fetch details for the selected releases concurrently, keeping their order
+        let selected: Vec<_> = releases
+            .iter()
+            .filter(|release| self.selection.includes(release.major, release.is_lts))
+            .collect();
+        let per_release: Vec<_> = stream::iter(&selected)
+            .map(|release| {
+                self.retry
+                    .run(|| provider.resolve_assets(&self.client, release, &self.target))
+            })
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut versions = Vec::new();
+        let mut missing = Vec::new();
+        for (release, assets) in selected.iter().zip(per_release) {
+            match assets {
+                Ok(assets) => versions.extend(assets),
+                Err(e) => missing.push(MissingRelease {
+                    vendor: provider.vendor().to_string(),
+                    major: Some(release.major),
+                    error: format!("{:#}", e),
+                }),
+            }
+        }
+
+        Ok((versions, missing))
+    }
+
+    /// Fetch every GA build of a feature release from every registered
+    /// provider. Like `fetch_available_versions`, providers that fail are
+    /// reported in `FetchReport::missing` and only a failure of all of them is
+    /// an error.
+    pub async fn fetch_release_history(&self, major: u32) -> Result<FetchReport> {
+        let key = self.metadata_key(&format!("history-{}", major));
+        if let Some(cached) = self.cached_metadata(&key)? {
+            return Ok(FetchReport {
+                versions: cached.versions,
+                missing: Vec::new(),
+                cached_at: Some(cached.fetched_at),
+            });
+        }
+
+        let per_provider = future::join_all(self.providers.iter().map(|provider| async {
+            let releases = self
+                .retry
+                .run(|| provider.list_releases(&self.client))
+                .await
+                .with_context(|| format!("Failed to list {} releases", provider.vendor()))?;
 
-            if let Ok(response) = self.client.get(&url).send().await {
-                if let Ok(assets) = response.json::<Vec<AdoptiumAsset>>().await {
-                    for asset in assets {
-                        let download_url = asset.binary.package.link;
-                        let checksum = asset.binary.package.checksum;
-
-                        versions.push(AvailableVersion {
-                            version: asset.version.semver,
-                            vendor: "Eclipse Temurin".to_string(),
-                            is_lts: available_info.available_lts_releases.contains(version),
-                            architecture: arch.to_string(),
-                            download_url,
-                            checksum: Some(checksum),
-                        });
-                    }
+            match releases.iter().find(|r| r.major == major) {
+                Some(release) => self
+                    .retry
+                    .run(|| provider.list_patch_releases(&self.client, release, &self.target))
+                    .await
+                    .with_context(|| {
+                        format!("Failed to list {} {} releases", provider.vendor(), major)
+                    }),
+                None => Ok(Vec::new()),
+            }
+        }))
+        .await;
+
+        let mut report = FetchReport::default();
+        let mut first_error = None;
+        let mut failed_providers = 0;
+
+        for (provider, result) in self.providers.iter().zip(per_provider) {
+            match result {
+                Ok(versions) => report.versions.extend(versions),
+                Err(e) => {
+                    failed_providers += 1;
+                    report.missing.push(MissingRelease {
+                        vendor: provider.vendor().to_string(),
+                        major: Some(major),
+                        error: format!("{:#}", e),
+                    });
+                    first_error.get_or_insert(e);
                 }
             }
         }
 
-        Ok(versions)
+        match first_error {
+            Some(e) if failed_providers == self.providers.len() => Err(e),
+            _ => {
+                // A history with gaps would hide those builds until the TTL runs out
+                if report.missing.is_empty() {
+                    self.store_metadata(&key, &report.versions)?;
+                }
+                Ok(report)
+            }
+        }
+    }
+
+    /// Name of the cached listing `kind` for this downloader's providers,
+    /// target, channel and release selection
+    fn metadata_key(&self, kind: &str) -> String {
+        let vendors: Vec<_> = self.providers.iter().map(|p| p.vendor()).collect();
+        let scope = format!(
+            "{}|{}|{}|{}|{}|{}",
+            vendors.join(","),
+            self.target.os,
+            self.target.arch,
+            self.target.image_type,
+            self.channel,
+            self.selection
+        );
+        let digest = hex::encode(Sha256::digest(scope.as_bytes()));
+        format!("{}-{}", kind, &digest[..16])
+    }
+
+    /// The cached listing to use instead of fetching, if any
+    fn cached_metadata(&self, key: &str) -> Result<Option<CachedMetadata>> {
+        let Some(metadata) = &self.metadata else {
+            if self.cache_mode == CacheMode::Offline {
+                anyhow::bail!("Offline mode needs a metadata cache");
+            }
+            return Ok(None);
+        };
+
+        match self.cache_mode {
+            CacheMode::Default => Ok(metadata.load_fresh(key)),
+            CacheMode::Refresh => Ok(None),
+            CacheMode::Offline => metadata.load(key).map(Some).ok_or_else(|| {
+                anyhow::anyhow!("No cached version list yet, run once without --offline")
+            }),
+        }
+    }
+
+    fn store_metadata(&self, key: &str, versions: &[AvailableVersion]) -> Result<()> {
+        match &self.metadata {
+            Some(metadata) => metadata.store(key, versions),
+            None => Ok(()),
+        }
+    }
+
+    fn provider_for(&self, version: &AvailableVersion) -> Option<&dyn Provider> {
+        self.providers
+            .iter()
+            .find(|p| p.provides(version))
+            .map(|p| p.as_ref())
     }
 
     /// Download and install a Java version
@@ -102,37 +613,348 @@ impl Downloader {
         installation_dir: &Path,
     ) -> Result<PathBuf> {
         let temp_dir = TempDir::new()?;
-        let filename = self.extract_filename(&version.download_url);
-        let temp_file = temp_dir.path().join(&filename);
 
-        // Download with progress bar
-        self.download_file(&version.download_url, &temp_file)
+        // Use the listed checksum, asking the provider when the listing had none
+        let checksum = match (&version.checksum, self.provider_for(version)) {
+            (Some(checksum), _) => Some(checksum.clone()),
+            // Offline the archive is found by its URL instead
+            (None, _) if self.cache_mode == CacheMode::Offline => None,
+            (None, Some(provider)) => {
+                self.retry
+                    .run(|| provider.fetch_checksum(&self.client, version))
+                    .await?
+            }
+            (None, None) => None,
+        };
+
+        let name_for = |release: &str| {
+            format!(
+                "{}-{}{}{}{}",
+                version.vendor.replace(" ", "_"),
+                release,
+                if version.channel == Channel::Ea {
+                    "-ea"
+                } else {
+                    ""
+                },
+                if version.javafx_bundled { "-fx" } else { "" },
+                version
+                    .image_type
+                    .badge()
+                    .map(|_| format!("-{}", version.image_type))
+                    .unwrap_or_default()
+            )
+        };
+        let name = name_for(&version.version);
+
+        // Held through download and extraction so parallel installs of the same
+        // version do not collide
+        let (lock_path, description) = Self::install_lock(installation_dir, &name);
+        let _lock = FileLock::exclusive_async(&lock_path, &description).await?;
+        let archive = self
+            .fetch_archive(&version.download_url, checksum.as_deref(), temp_dir.path())
+            .await?;
+
+        if version.is_feature_only() {
+            // Named after the exact version, so later updates install next to it
+            return self.install_staged_with(&archive, &name, installation_dir, |jdk_root| {
+                Ok(name_for(&JavaDetector::inspect(jdk_root)?.version))
+            });
+        }
+        self.install_staged(&archive, &name, installation_dir)
+    }
+
+    /// Install a JDK archive that did not come from a provider, e.g. one copied
+    /// from an internal artifact store. Returns the unpacked JDK root.
+    pub fn install_archive(
+        &self,
+        archive: &Path,
+        checksum: Option<&str>,
+        installation_dir: &Path,
+    ) -> Result<PathBuf> {
+        let archive = archive.to_path_buf();
+        if !archive.is_file() {
+            anyhow::bail!("Archive {} not found", archive.display());
+        }
+
+        if let Some(checksum) = checksum {
+            self.verify_checksum(&archive, checksum)?;
+        }
+
+        let file_name = archive
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("archive");
+        let stem = [".tar.gz", ".tgz", ".zip"]
+            .iter()
+            .find_map(|ext| file_name.strip_suffix(ext))
+            .unwrap_or(file_name);
+
+        let (lock_path, description) = Self::install_lock(installation_dir, stem);
+        let _lock = FileLock::exclusive(&lock_path, &description)?;
+        self.install_staged(&archive, stem, installation_dir)
+    }
+
+    /// Lock file guarding against other processes installing into
+    /// `installation_dir/name`, and a description of it for the wait message
+    fn install_lock(installation_dir: &Path, name: &str) -> (PathBuf, String) {
+        (
+            installation_dir
+                .join(LOCK_DIR)
+                .join(format!("{}.lock", name)),
+            format!("the {} install", name),
+        )
+    }
+
+    /// Unpack `archive` into a staging directory and move it to
+    /// `installation_dir/name` only once it holds a JDK, so a failed or
+    /// interrupted install never leaves a partial installation behind.
+    /// Returns the JDK root inside the installed directory.
+    fn install_staged(
+        &self,
+        archive: &Path,
+        name: &str,
+        installation_dir: &Path,
+    ) -> Result<PathBuf> {
+        let install_dir = installation_dir.join(name);
+        if install_dir.exists() {
+            anyhow::bail!("{} is already installed", install_dir.display());
+        }
+
+        self.install_staged_with(archive, name, installation_dir, |_| Ok(name.to_string()))
+    }
+
+    /// `install_staged` for archives whose directory name depends on the
+    /// unpacked JDK: `name` is given the JDK root in the staging directory
+    fn install_staged_with(
+        &self,
+        archive: &Path,
+        staging_prefix: &str,
+        installation_dir: &Path,
+        name: impl FnOnce(&Path) -> Result<String>,
+    ) -> Result<PathBuf> {
+        // Keeps `clean_staging` away from this install's staging directory
+        let _staging_lock = FileLock::shared(
+            &installation_dir.join(LOCK_DIR).join("staging.lock"),
+            "the staging directory",
+        )?;
+
+        // Staging next to the installations keeps the final rename atomic
+        let staging_root = installation_dir.join(STAGING_DIR);
+        fs::create_dir_all(&staging_root)?;
+        let staging = tempfile::Builder::new()
+            .prefix(&format!("{}-", staging_prefix))
+            .tempdir_in(&staging_root)?;
+
+        self.extract_archive(archive, staging.path())?;
+        let jdk_root = self.find_jdk_root(staging.path())?;
+        let relative = jdk_root.strip_prefix(staging.path())?.to_path_buf();
+
+        let install_dir = installation_dir.join(name(&jdk_root)?);
+        if install_dir.exists() {
+            anyhow::bail!("{} is already installed", install_dir.display());
+        }
+
+        fs::rename(staging.path(), &install_dir)
+            .with_context(|| format!("Failed to move the JDK into {}", install_dir.display()))?;
+
+        Ok(install_dir.join(relative))
+    }
+
+    /// Remove what interrupted installs left in the staging directory,
+    /// returning how many were cleaned up. Nothing is removed while another
+    /// process is installing.
+    pub fn clean_staging(installation_dir: &Path) -> Result<usize> {
+        let staging_root = installation_dir.join(STAGING_DIR);
+        if !staging_root.is_dir() {
+            return Ok(0);
+        }
+        let Some(_lock) =
+            FileLock::try_exclusive(&installation_dir.join(LOCK_DIR).join("staging.lock"))?
+        else {
+            return Ok(0);
+        };
+
+        let mut removed = 0;
+        for entry in fs::read_dir(&staging_root)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+            removed += 1;
+        }
+        fs::remove_dir(&staging_root)?;
+
+        Ok(removed)
+    }
+
+    /// Download an archive from an arbitrary URL and install it like
+    /// `install_archive`
+    pub async fn install_from_url(
+        &self,
+        url: &str,
+        checksum: Option<&str>,
+        installation_dir: &Path,
+    ) -> Result<PathBuf> {
+        let temp_dir = TempDir::new()?;
+        let archive = self.fetch_archive(url, checksum, temp_dir.path()).await?;
+
+        // fetch_archive already verified the checksum
+        self.install_archive(&archive, None, installation_dir)
+    }
+
+    /// Download an archive and verify its checksum, going through the archive
+    /// cache when one is configured: cached archives are reused and unfinished
+    /// downloads are resumed. Without a cache the archive lands in `scratch_dir`.
+    /// Offline only archives already in the cache can be used.
+    async fn fetch_archive(
+        &self,
+        url: &str,
+        checksum: Option<&str>,
+        scratch_dir: &Path,
+    ) -> Result<PathBuf> {
+        // Validates the checksum before it is used in any path
+        let key = ArchiveCache::key(url, checksum)?;
+
+        if self.cache_mode == CacheMode::Offline {
+            let archive = self
+                .cache
+                .as_ref()
+                .and_then(|cache| {
+                    checksum
+                        .and_then(|_| cache.lookup(&key))
+                        .or_else(|| cache.lookup_url(url))
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{} has not been downloaded yet and is unavailable offline",
+                        url
+                    )
+                })?;
+            if let Some(checksum) = checksum {
+                self.verify_checksum(&archive, checksum)?;
+            }
+            println!("{}", console::style("Using cached archive").dim());
+            return Ok(archive);
+        }
+
+        let Some(cache) = &self.cache else {
+            let archive = self
+                .retry
+                .run(|| self.download_file(url, scratch_dir))
+                .await?;
+            if let Some(checksum) = checksum {
+                self.verify_checksum(&archive, checksum)?;
+            }
+            return Ok(archive);
+        };
+
+        // Two processes must not append to the same partial download
+        let _lock =
+            FileLock::exclusive_async(&cache.lock_path(&key), "a download of the same archive")
+                .await?;
+        if let Some(archive) = cache.lookup(&key) {
+            match checksum.map(|checksum| self.verify_checksum(&archive, checksum)) {
+                // A corrupted cache entry is downloaded again
+                Some(Err(_)) => cache.evict(&key)?,
+                _ => {
+                    println!("{}", console::style("Using cached archive").dim());
+                    return Ok(archive);
+                }
+            }
+        }
+
+        fs::create_dir_all(cache.entry_dir(&key))?;
+        // Retries pick up where the failed attempt stopped
+        let partial = cache.partial_path(&key);
+        let file_name = self
+            .retry
+            .run(|| self.download_resumable(url, &partial))
             .await?;
+        let archive = cache.complete(&key, &file_name)?;
+        cache.record_source(&key, url)?;
 
-        // Verify checksum if available
-        if let Some(ref checksum) = version.checksum {
-            self.verify_checksum(&temp_file, checksum)?;
+        if let Some(checksum) = checksum {
+            if let Err(e) = self.verify_checksum(&archive, checksum) {
+                cache.evict(&key)?;
+                return Err(e);
+            }
         }
 
-        // Extract archive
-        let extract_dir = installation_dir.join(format!(
-            "{}-{}",
-            version.vendor.replace(" ", "_"),
-            version.version
-        ));
-        fs::create_dir_all(&extract_dir)?;
+        Ok(archive)
+    }
+
+    /// Download `url` into `partial`, continuing from its current length with
+    /// an HTTP Range request. Returns the archive's file name.
+    async fn download_resumable(&self, url: &str, partial: &Path) -> Result<String> {
+        let mut offset = fs::metadata(partial).map(|m| m.len()).unwrap_or(0);
+
+        let mut request = self.download_client.get(url);
+        if offset > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+        }
+        let mut response = self.send_download(request).await?;
+
+        // The partial file is no prefix of the archive (e.g. it changed upstream)
+        if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            fs::remove_file(partial)?;
+            offset = 0;
+            response = self.send_download(self.download_client.get(url)).await?;
+        }
+        let response = response.error_for_status()?;
+
+        // Servers without Range support answer 200 with the whole archive
+        let mut file = if offset > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+            println!(
+                "{}",
+                console::style(format!("Resuming download at {} bytes", offset)).dim()
+            );
+            OpenOptions::new().append(true).open(partial)?
+        } else {
+            offset = 0;
+            File::create(partial)?
+        };
+
+        let file_name = archive_file_name(url, &response);
+        self.write_response(response, &mut file, offset).await?;
+
+        Ok(file_name)
+    }
+
+    /// Download `url` into `dest_dir`, named as described in `archive_file_name`
+    async fn download_file(&self, url: &str, dest_dir: &Path) -> Result<PathBuf> {
+        let response = self
+            .send_download(self.download_client.get(url))
+            .await?
+            .error_for_status()?;
+        let dest = dest_dir.join(archive_file_name(url, &response));
 
-        self.extract_archive(&temp_file, &extract_dir)?;
+        let mut file = File::create(&dest)?;
+        self.write_response(response, &mut file, 0).await?;
 
-        // Find the actual JDK directory (might be nested)
-        let jdk_dir = self.find_jdk_root(&extract_dir)?;
+        Ok(dest)
+    }
 
-        Ok(jdk_dir)
+    /// Send a download request, giving up when no response arrives within the
+    /// read timeout
+    async fn send_download(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let read_timeout = Duration::from_secs(self.network.read_timeout_secs);
+        Ok(tokio::time::timeout(read_timeout, request.send())
+            .await
+            .map_err(|_| ReadTimeout(read_timeout))??)
     }
 
-    async fn download_file(&self, url: &str, dest: &PathBuf) -> Result<()> {
-        let response = self.client.get(url).send().await?;
-        let total_size = response.content_length().unwrap_or(0);
+    /// Stream a response body into `file` with a progress bar, `offset` bytes
+    /// of which were downloaded before
+    async fn write_response(
+        &self,
+        response: reqwest::Response,
+        file: &mut File,
+        offset: u64,
+    ) -> Result<()> {
+        let total_size = response.content_length().unwrap_or(0) + offset;
 
         let pb = ProgressBar::new(total_size);
         pb.set_style(
@@ -141,11 +963,19 @@ impl Downloader {
                 .progress_chars("#>-"),
         );
 
-        let mut file = File::create(dest)?;
-        let mut downloaded: u64 = 0;
+        let mut downloaded = offset;
+        pb.set_position(downloaded);
         let mut stream = response.bytes_stream();
 
-        while let Some(item) = stream.next().await {
+        // A stalled connection fails after the read timeout instead of hanging
+        let read_timeout = Duration::from_secs(self.network.read_timeout_secs);
+        loop {
+            let item = tokio::time::timeout(read_timeout, stream.next())
+                .await
+                .map_err(|_| ReadTimeout(read_timeout))?;
+            let Some(item) = item else {
+                break;
+            };
             let chunk = item?;
             file.write_all(&chunk)?;
             downloaded += chunk.len() as u64;
@@ -172,119 +1002,94 @@ impl Downloader {
         Ok(())
     }
 
-    fn extract_archive(&self, archive: &PathBuf, dest: &PathBuf) -> Result<()> {
+    fn extract_archive(&self, archive: &Path, dest: &Path) -> Result<()> {
         let pb = ProgressBar::new_spinner();
         pb.set_message("Extracting archive...");
         pb.enable_steady_tick(std::time::Duration::from_millis(100));
 
-        let extension = archive.extension().and_then(|s| s.to_str()).unwrap_or("");
-
-        match extension {
-            "zip" => self.extract_zip(archive, dest)?,
-            "gz" => self.extract_tar_gz(archive, dest)?,
-            _ => anyhow::bail!("Unsupported archive format: {}", extension),
-        }
+        extract::extract_archive(archive, dest, extract::MAX_UNPACKED_SIZE)?;
 
         pb.finish_with_message("Extraction complete");
         Ok(())
     }
 
-    fn extract_zip(&self, archive: &Path, dest: &Path) -> Result<()> {
-        let file = File::open(archive)?;
-        let mut archive = zip::ZipArchive::new(file)?;
-
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            let outpath = dest.join(file.name());
+    /// The directory holding `bin/java` among the unpacked files: the archive
+    /// root, its top-level directory, or a macOS bundle's `Contents/Home`
+    fn find_jdk_root(&self, extract_dir: &Path) -> Result<PathBuf> {
+        let java_exe = if cfg!(windows) { "java.exe" } else { "java" };
+        let is_jdk_root = |dir: &Path| dir.join("bin").join(java_exe).is_file();
 
-            if file.is_dir() {
-                fs::create_dir_all(&outpath)?;
-            } else {
-                if let Some(p) = outpath.parent() {
-                    fs::create_dir_all(p)?;
-                }
-                let mut outfile = File::create(&outpath)?;
-                std::io::copy(&mut file, &mut outfile)?;
-            }
-
-            // Set permissions on Unix
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                if let Some(mode) = file.unix_mode() {
-                    fs::set_permissions(&outpath, fs::Permissions::from_mode(mode))?;
-                }
-            }
+        if is_jdk_root(extract_dir) {
+            return Ok(extract_dir.to_path_buf());
         }
 
-        Ok(())
-    }
-
-    fn extract_tar_gz(&self, archive: &PathBuf, dest: &PathBuf) -> Result<()> {
-        let tar_gz = File::open(archive)?;
-        let tar = flate2::read::GzDecoder::new(tar_gz);
-        let mut archive = tar::Archive::new(tar);
-        archive.unpack(dest)?;
-        Ok(())
-    }
-
-    fn find_jdk_root(&self, extract_dir: &PathBuf) -> Result<PathBuf> {
-        // Sometimes archives have a top-level directory
         for entry in fs::read_dir(extract_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_dir() {
-                let bin_dir = path.join("bin");
-                let java_exe = if cfg!(windows) {
-                    bin_dir.join("java.exe")
-                } else {
-                    bin_dir.join("java")
-                };
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
 
-                if java_exe.exists() {
-                    return Ok(path);
+            for candidate in [path.clone(), path.join("Contents").join("Home")] {
+                if is_jdk_root(&candidate) {
+                    return Ok(candidate);
                 }
             }
         }
 
-        // If not found, assume extract_dir is the root
-        Ok(extract_dir.clone())
-    }
-
-    fn extract_filename(&self, url: &str) -> String {
-        url.split('/')
-            .next_back()
-            .unwrap_or("download.zip")
-            .to_string()
+        anyhow::bail!(
+            "The archive does not contain a JDK (no bin/{} found)",
+            java_exe
+        )
     }
 }
 
-// Adoptium API structures
-#[derive(Debug, Deserialize)]
-struct AdoptiumAvailableReleases {
-    available_releases: Vec<u32>,
-    available_lts_releases: Vec<u32>,
-}
+/// File name to save a download under, which decides how it is unpacked. The
+/// requested URL's name comes first: redirects often end at signed storage
+/// URLs (e.g. GitHub release assets) whose last segment is an opaque ID
+/// followed by a query string. Links that do not name an archive, like
+/// foojay's download endpoints, fall back to `Content-Disposition` and then
+/// to the final URL.
+fn archive_file_name(url: &str, response: &reqwest::Response) -> String {
+    let requested = url_file_name(url);
+    let disposition = response
+        .headers()
+        .get(reqwest::header::CONTENT_DISPOSITION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(disposition_file_name);
+    let redirected = url_file_name(response.url().as_str());
 
-#[derive(Debug, Deserialize)]
-struct AdoptiumAsset {
-    binary: AdoptiumBinary,
-    version: AdoptiumVersion,
+    let archive = [&requested, &disposition, &redirected]
+        .into_iter()
+        .flatten()
+        .find(|name| is_archive_name(name));
+    archive
+        .or(requested.as_ref())
+        .cloned()
+        .unwrap_or_else(|| "download.zip".to_string())
 }
 
-#[derive(Debug, Deserialize)]
-struct AdoptiumBinary {
-    package: AdoptiumPackage,
+/// Last segment of the URL's path, without query or fragment
+fn url_file_name(url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let name = url.path_segments()?.next_back()?;
+    (!name.is_empty()).then(|| name.to_string())
 }
 
-#[derive(Debug, Deserialize)]
-struct AdoptiumPackage {
-    link: String,
-    checksum: String,
+/// The `filename` of a `Content-Disposition: attachment; filename="..."`
+/// header, without any directories
+fn disposition_file_name(value: &str) -> Option<String> {
+    let name = value.split(';').map(str::trim).find_map(|part| {
+        part.strip_prefix("filename*=")
+            .map(|name| name.rsplit("''").next().unwrap_or(name))
+            .or_else(|| part.strip_prefix("filename="))
+    })?;
+    let name = name.trim_matches('"').rsplit(['/', '\\']).next()?;
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
 }
 
-#[derive(Debug, Deserialize)]
-struct AdoptiumVersion {
-    semver: String,
+fn is_archive_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    [".tar.gz", ".tgz", ".zip"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
 }
@@ -0,0 +1,238 @@
+//! Archive extraction that keeps every entry inside the destination directory.
+//!
+//! Entries with absolute paths or `..` components are rejected, symlinks may
+//! only point at other files in the archive, nothing is written through a
+//! symlink, and the total unpacked size is capped.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// Upper bound on the bytes an archive may unpack to. Full JDKs unpack to a
+/// few hundred megabytes, debug images to a little over one gigabyte.
+pub const MAX_UNPACKED_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Unpack a `.zip`, `.tar.gz` or `.tgz` archive into `dest`, refusing entries
+/// that would end up outside of it and archives larger than `max_size`
+pub fn extract_archive(archive: &Path, dest: &Path, max_size: u64) -> Result<()> {
+    let extension = archive.extension().and_then(|s| s.to_str()).unwrap_or("");
+    let mut limit = SizeLimit::new(max_size);
+
+    match extension {
+        "zip" => extract_zip(archive, dest, &mut limit),
+        "gz" | "tgz" => extract_tar_gz(archive, dest, &mut limit),
+        _ => anyhow::bail!("Unsupported archive format: {}", extension),
+    }
+}
+
+fn extract_zip(archive: &Path, dest: &Path, limit: &mut SizeLimit) -> Result<()> {
+    let file = File::open(archive)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name().to_string();
+        let Some(relative) = entry_path(Path::new(&name))? else {
+            continue;
+        };
+        let outpath = prepare_entry(dest, &relative)?;
+        let mode = file.unix_mode();
+
+        if mode.is_some_and(|mode| mode & 0o170000 == 0o120000) {
+            // Zip stores a symlink's target as the entry's contents
+            let mut target = String::new();
+            file.read_to_string(&mut target)?;
+            create_symlink(&relative, Path::new(&target), &outpath)?;
+            continue;
+        }
+
+        if file.is_dir() {
+            fs::create_dir_all(&outpath)?;
+        } else {
+            limit.copy(&mut file, &outpath)?;
+        }
+
+        set_mode(&outpath, mode)?;
+    }
+
+    Ok(())
+}
+
+fn extract_tar_gz(archive: &Path, dest: &Path, limit: &mut SizeLimit) -> Result<()> {
+    let tar_gz = File::open(archive)?;
+    let tar = flate2::read::GzDecoder::new(tar_gz);
+    let mut archive = tar::Archive::new(tar);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(relative) = entry_path(&entry.path()?)? else {
+            continue;
+        };
+        let outpath = prepare_entry(dest, &relative)?;
+        let mode = entry.header().mode().ok();
+
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => {
+                fs::create_dir_all(&outpath)?;
+                set_mode(&outpath, mode)?;
+            }
+            tar::EntryType::Regular | tar::EntryType::Continuous => {
+                limit.copy(&mut entry, &outpath)?;
+                set_mode(&outpath, mode)?;
+            }
+            tar::EntryType::Symlink => {
+                let target = entry
+                    .link_name()?
+                    .with_context(|| format!("Symlink {} has no target", relative.display()))?;
+                create_symlink(&relative, &target, &outpath)?;
+            }
+            tar::EntryType::Link => {
+                // Hard link targets are relative to the archive root
+                let target = entry
+                    .link_name()?
+                    .with_context(|| format!("Hard link {} has no target", relative.display()))?;
+                let target = entry_path(&target)?
+                    .with_context(|| format!("Hard link {} has no target", relative.display()))?;
+                let original = real_path(dest, &target)?;
+                if !fs::symlink_metadata(&original).is_ok_and(|m| m.is_file()) {
+                    anyhow::bail!(
+                        "Hard link {} does not point to a file in the archive",
+                        relative.display()
+                    );
+                }
+                fs::hard_link(original, &outpath)?;
+            }
+            // Devices, FIFOs and metadata-only entries have no place in a JDK
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The path of an archive entry relative to the destination, or `None` for
+/// the archive root itself
+fn entry_path(name: &Path) -> Result<Option<PathBuf>> {
+    let mut relative = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                anyhow::bail!(
+                    "Archive entry {} points outside the destination",
+                    name.display()
+                );
+            }
+        }
+    }
+
+    Ok((!relative.as_os_str().is_empty()).then_some(relative))
+}
+
+/// `relative` inside `dest`, provided no directory leading to it is a symlink
+fn real_path(dest: &Path, relative: &Path) -> Result<PathBuf> {
+    let mut current = dest.to_path_buf();
+    if let Some(parent) = relative.parent() {
+        for component in parent.components() {
+            current.push(component);
+            if fs::symlink_metadata(&current).is_ok_and(|m| m.file_type().is_symlink()) {
+                anyhow::bail!(
+                    "Archive entry {} is written through a symlink",
+                    relative.display()
+                );
+            }
+        }
+    }
+    Ok(dest.join(relative))
+}
+
+/// Where to write the entry at `relative`, with its parent directories created
+fn prepare_entry(dest: &Path, relative: &Path) -> Result<PathBuf> {
+    let outpath = real_path(dest, relative)?;
+
+    // A later entry replaces an earlier link instead of writing through it
+    if fs::symlink_metadata(&outpath).is_ok_and(|m| m.file_type().is_symlink()) {
+        fs::remove_file(&outpath)?;
+    }
+
+    if let Some(parent) = outpath.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(outpath)
+}
+
+/// Create the symlink at `relative` (written to `outpath`), provided `target`
+/// resolves to a path inside the archive. Targets may climb with leading `..`
+/// components and then only descend, so links to other links cannot escape.
+fn create_symlink(relative: &Path, target: &Path, outpath: &Path) -> Result<()> {
+    let escapes = || {
+        anyhow::anyhow!(
+            "Symlink {} -> {} points outside the destination",
+            relative.display(),
+            target.display()
+        )
+    };
+
+    let mut depth = relative.components().count() - 1;
+    let mut descended = false;
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => descended = true,
+            Component::CurDir => {}
+            Component::ParentDir if !descended && depth > 0 => depth -= 1,
+            _ => return Err(escapes()),
+        }
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(target, outpath)?;
+
+    // Windows JDK archives are zips without links
+    #[cfg(not(unix))]
+    let _ = outpath;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: Option<u32>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    if let Some(mode) = mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o7777))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: Option<u32>) -> Result<()> {
+    Ok(())
+}
+
+/// Bytes still allowed to be written while unpacking one archive
+struct SizeLimit {
+    max: u64,
+    remaining: u64,
+}
+
+impl SizeLimit {
+    fn new(max: u64) -> Self {
+        Self {
+            max,
+            remaining: max,
+        }
+    }
+
+    /// Write `reader` to a new file at `path`, counting the bytes actually
+    /// unpacked rather than trusting the sizes the archive declares
+    fn copy(&mut self, reader: &mut impl Read, path: &Path) -> Result<()> {
+        let mut file = File::create(path)?;
+        let written = io::copy(&mut reader.take(self.remaining + 1), &mut file)?;
+        if written > self.remaining {
+            anyhow::bail!("Archive unpacks to more than {} bytes", self.max);
+        }
+        self.remaining -= written;
+        Ok(())
+    }
+}
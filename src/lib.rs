@@ -1,12 +1,26 @@
 // Library exports for testing and potential library usage
 
+pub mod cache;
 pub mod config;
 pub mod detector;
 pub mod downloader;
+pub mod extract;
+pub mod lock;
+pub mod migrations;
 pub mod path_manager;
+pub mod project;
+pub mod providers;
+pub mod query;
+pub mod retry;
+pub mod shell;
+pub mod version;
 
 // Re-export commonly used types
-pub use config::{Config, JavaVersion};
+pub use config::{Config, ImageType, JavaVersion, MetadataBackend, ReleaseSelection};
 pub use detector::JavaDetector;
 pub use downloader::{AvailableVersion, Downloader};
 pub use path_manager::PathManager;
+pub use providers::{
+    CorrettoProvider, FoojayProvider, GraalVmCeProvider, OracleGraalVmProvider, Provider,
+    TemurinProvider, ZuluProvider,
+};
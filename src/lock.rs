@@ -0,0 +1,131 @@
+//! Advisory file locks that keep concurrent jaman processes from overwriting
+//! each other's config changes and installs
+
+use anyhow::{Context, Result};
+use std::fs::{self, File, TryLockError};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long to wait for a lock when `JAMAN_LOCK_TIMEOUT` is not set
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Delay between attempts to take a lock held by another process
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A lock on a file, released when dropped or when the process exits
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Take the exclusive lock on `path`, waiting for other holders
+    pub fn exclusive(path: &Path, description: &str) -> Result<Self> {
+        Self::acquire(path, description, false, lock_timeout())
+    }
+
+    /// Take a shared lock on `path`, waiting while someone holds it exclusively
+    pub fn shared(path: &Path, description: &str) -> Result<Self> {
+        Self::acquire(path, description, true, lock_timeout())
+    }
+
+    /// `exclusive` for async code: the wait runs on a blocking thread, so it
+    /// does not stall the runtime's workers
+    pub async fn exclusive_async(path: &Path, description: &str) -> Result<Self> {
+        let (path, description) = (path.to_path_buf(), description.to_string());
+        tokio::task::spawn_blocking(move || Self::exclusive(&path, &description)).await?
+    }
+
+    /// Take the exclusive lock on `path` only if nobody else holds it
+    pub fn try_exclusive(path: &Path) -> Result<Option<Self>> {
+        let file = open(path)?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    /// Lock `path`, printing a note while waiting and giving up after `timeout`.
+    /// `description` names what the lock protects, e.g. "the jaman config".
+    /// The note goes to stderr, since stdout may be evaluated by a shell.
+    /// This blocks the thread; async code uses `exclusive_async`.
+    pub fn acquire(
+        path: &Path,
+        description: &str,
+        shared: bool,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let file = open(path)?;
+        let started = Instant::now();
+        let mut announced = false;
+
+        loop {
+            let attempt = if shared {
+                file.try_lock_shared()
+            } else {
+                file.try_lock()
+            };
+
+            match attempt {
+                Ok(()) => return Ok(Self { file }),
+                Err(TryLockError::Error(e)) => {
+                    return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+                }
+                Err(TryLockError::WouldBlock) => {}
+            }
+
+            if started.elapsed() >= timeout {
+                anyhow::bail!(
+                    "Timed out after {}s waiting for another jaman process to release {} ({})",
+                    timeout.as_secs(),
+                    description,
+                    path.display()
+                );
+            }
+
+            if !announced {
+                eprintln!(
+                    "{}",
+                    console::style(format!(
+                        "Waiting for another jaman process to release {}...",
+                        description
+                    ))
+                    .yellow()
+                );
+                announced = true;
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+fn open(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .with_context(|| format!("Failed to open lock file {}", path.display()))
+}
+
+/// Lock timeout, overridable in seconds with `JAMAN_LOCK_TIMEOUT`
+fn lock_timeout() -> Duration {
+    std::env::var("JAMAN_LOCK_TIMEOUT")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TIMEOUT)
+}
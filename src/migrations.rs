@@ -0,0 +1,126 @@
+//! Upgrades for config.toml files written by older versions of jaman.
+//!
+//! Layout changes that `serde(default)` cannot express bump `SCHEMA_VERSION`
+//! by appending a step to `MIGRATIONS`; `Config` loading runs every step the
+//! file has not seen yet, oldest first.
+
+use crate::config::{self, ImageType};
+use anyhow::{Context, Result};
+use std::path::Path;
+use toml::{Table, Value};
+
+type Migration = fn(&mut Table) -> Result<()>;
+
+/// Step `n` upgrades a config from schema version `n` to `n + 1`
+const MIGRATIONS: &[Migration] = &[add_download_dir, add_installation_ids];
+
+/// Schema version of configs written by this build
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Upgrade a parsed config in place to `SCHEMA_VERSION`, returning the schema
+/// version it was written with. Configs without a `schema_version` predate it
+/// and count as version 0.
+pub fn migrate(table: &mut Table) -> Result<u32> {
+    let found = match table.get("schema_version") {
+        None => 0,
+        Some(Value::Integer(version)) => u32::try_from(*version)
+            .map_err(|_| anyhow::anyhow!("Invalid schema_version {}", version))?,
+        Some(other) => anyhow::bail!("Invalid schema_version {}", other),
+    };
+
+    if found > SCHEMA_VERSION {
+        anyhow::bail!(
+            "The config uses schema version {}, but this jaman only understands up to {}; \
+             please upgrade jaman",
+            found,
+            SCHEMA_VERSION
+        );
+    }
+
+    for (version, step) in MIGRATIONS.iter().enumerate().skip(found as usize) {
+        step(table).with_context(|| {
+            format!(
+                "Failed to upgrade the config from schema version {}",
+                version
+            )
+        })?;
+    }
+    table.insert(
+        "schema_version".to_string(),
+        Value::Integer(SCHEMA_VERSION.into()),
+    );
+
+    Ok(found)
+}
+
+/// 0 -> 1: configs from 0.1.0 have no `download_dir`. Use `downloads` next to
+/// the installation directory, which is where the default layout puts it.
+fn add_download_dir(table: &mut Table) -> Result<()> {
+    if table.contains_key("download_dir") {
+        return Ok(());
+    }
+
+    let installation_dir = table
+        .get("installation_dir")
+        .and_then(Value::as_str)
+        .context("installation_dir is missing")?;
+    let installation_dir = Path::new(installation_dir);
+    let download_dir = installation_dir
+        .parent()
+        .unwrap_or(installation_dir)
+        .join("downloads");
+
+    table.insert(
+        "download_dir".to_string(),
+        Value::String(download_dir.to_string_lossy().into_owned()),
+    );
+    Ok(())
+}
+
+/// 1 -> 2: installations get stable IDs, and the active installation is
+/// referenced by ID instead of by a version string that several vendors'
+/// builds can share. Of several installs of the active version, the first
+/// one listed becomes active, which is the one 0.1.0 would have picked.
+fn add_installation_ids(table: &mut Table) -> Result<()> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut active_id = None;
+    let active_version = table
+        .remove("active_version")
+        .and_then(|v| v.as_str().map(str::to_string));
+
+    if let Some(Value::Array(versions)) = table.get_mut("installed_versions") {
+        for entry in versions.iter_mut() {
+            let entry = entry
+                .as_table_mut()
+                .context("installed_versions holds a non-table entry")?;
+            let field = |name: &str| entry.get(name).and_then(Value::as_str).unwrap_or("");
+            let image_type = match entry.get("image_type") {
+                Some(value) => value.clone().try_into()?,
+                None => ImageType::default(),
+            };
+            let javafx_bundled = entry
+                .get("javafx_bundled")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+
+            let base = config::installation_id(
+                field("vendor"),
+                field("version"),
+                field("architecture"),
+                image_type,
+                javafx_bundled,
+            );
+            let id = config::unique_id(base, ids.iter().map(String::as_str));
+            if active_id.is_none() && active_version.as_deref() == Some(field("version")) {
+                active_id = Some(id.clone());
+            }
+            entry.insert("id".to_string(), Value::String(id.clone()));
+            ids.push(id);
+        }
+    }
+
+    if let Some(id) = active_id {
+        table.insert("active_id".to_string(), Value::String(id));
+    }
+    Ok(())
+}
@@ -189,7 +189,9 @@ impl PathManager {
             }
         }
 
-        println!("⚠️  Please restart your terminal or run: source ~/.bashrc");
+        println!(
+            "⚠️  New terminals pick this up; to use it in this one, run: eval \"$(jaman env)\""
+        );
 
         Ok(())
     }
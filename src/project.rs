@@ -0,0 +1,214 @@
+//! Per-project Java versions, read from `.java-version` files and, for teams
+//! migrating from other tools, SDKMAN's `.sdkmanrc` and asdf's `.tool-versions`
+
+use crate::config::{Config, JavaVersion};
+use crate::providers::CorrettoProvider;
+use crate::query::VersionQuery;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File written by `jaman local`
+pub const JAVA_VERSION_FILE: &str = ".java-version";
+
+/// Version files in the order they are looked for within one directory
+const VERSION_FILES: &[&str] = &[JAVA_VERSION_FILE, ".sdkmanrc", ".tool-versions"];
+
+/// A Java version requested by a file in a project directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectVersion {
+    /// Installation ID or version query, e.g. "17" or "temurin@21.0.2"
+    pub spec: String,
+    /// File the version was read from
+    pub file: PathBuf,
+    /// Whether only builds bundling JavaFX qualify, as for SDKMAN's
+    /// "21.0.2.fx-zulu"
+    pub javafx: bool,
+}
+
+impl ProjectVersion {
+    /// The version requested for `dir`, from the nearest directory, walking
+    /// up from `dir`, that has a version file naming Java
+    pub fn find(dir: &Path) -> Result<Option<Self>> {
+        for ancestor in dir.ancestors() {
+            for name in VERSION_FILES {
+                let file = ancestor.join(name);
+                if file.is_file() {
+                    if let Some(version) = Self::read(&file)? {
+                        return Ok(Some(version));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read one version file. `.sdkmanrc` and `.tool-versions` files that do
+    /// not mention Java give `None`.
+    pub fn read(file: &Path) -> Result<Option<Self>> {
+        let content = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let name = file
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        let spec = match name.as_ref() {
+            ".sdkmanrc" => parse_sdkmanrc(&content),
+            ".tool-versions" => parse_tool_versions(&content)
+                .with_context(|| format!("Failed to read {}", file.display()))?
+                .map(|spec| (spec, false)),
+            _ => content
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|spec| (spec.to_string(), false)),
+        };
+
+        Ok(spec.map(|(spec, javafx)| Self {
+            spec,
+            file: file.to_path_buf(),
+            javafx,
+        }))
+    }
+
+    /// The installed version this project asks for
+    pub fn resolve<'a>(&self, config: &'a Config) -> Result<&'a JavaVersion> {
+        if !self.javafx {
+            return config.get_version(&self.spec).with_context(|| {
+                format!(
+                    "Java {} requested by {} is not available; install it with 'jaman install {}'",
+                    self.spec,
+                    self.file.display(),
+                    self.spec
+                )
+            });
+        }
+
+        let query: VersionQuery = self.spec.parse()?;
+        let with_javafx = config
+            .installed_versions
+            .iter()
+            .filter(|v| v.javafx_bundled);
+        if let Some(version) = query.select(with_javafx)? {
+            return Ok(version);
+        }
+
+        let without_javafx = query.select(&config.installed_versions).ok().flatten();
+        anyhow::bail!(
+            "Java {} with JavaFX requested by {} is not available{}; install it with 'jaman install --javafx {}'",
+            self.spec,
+            self.file.display(),
+            if without_javafx.is_some() {
+                " (the installed matches do not bundle JavaFX)"
+            } else {
+                ""
+            },
+            self.spec
+        )
+    }
+}
+
+/// The version in effect in `dir`: the project's, if a version file asks for
+/// one, otherwise the globally active version
+pub fn effective_version<'a>(
+    config: &'a Config,
+    dir: &Path,
+) -> Result<Option<(&'a JavaVersion, Option<ProjectVersion>)>> {
+    match ProjectVersion::find(dir)? {
+        Some(project) => Ok(Some((project.resolve(config)?, Some(project)))),
+        None => Ok(config.active().map(|version| (version, None))),
+    }
+}
+
+/// `java=21.0.2-tem` from an `.sdkmanrc` file, as a version query and
+/// whether it names a JavaFX build
+fn parse_sdkmanrc(content: &str) -> Option<(String, bool)> {
+    let identifier = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "java").then(|| value.trim())
+        })?;
+
+    // SDKMAN identifiers are "<version>-<vendor>", e.g. "21.0.2-tem" or
+    // "21.0.2.fx-zulu" for JavaFX builds
+    let (version, vendor) = identifier.rsplit_once('-').unwrap_or((identifier, ""));
+    let (version, javafx) = match version.strip_suffix(".fx") {
+        Some(version) => (version, true),
+        None => (version, false),
+    };
+    let vendor = match vendor {
+        "tem" => Some("temurin"),
+        "amzn" => Some("corretto"),
+        "zulu" => Some("zulu"),
+        "librca" | "nik" => Some("liberica"),
+        "sem" => Some("semeru"),
+        "ms" => Some("microsoft"),
+        "sapmchn" => Some("sapmachine"),
+        "albba" => Some("dragonwell"),
+        "graalce" => Some("graalvm ce"),
+        "graal" => Some("oracle graalvm"),
+        _ => None,
+    };
+
+    let spec = match vendor {
+        Some(vendor) => format!("{}@{}", vendor, version),
+        None => version.to_string(),
+    };
+    Some((spec, javafx))
+}
+
+/// `java temurin-21.0.2+13.0.LTS` from an asdf `.tool-versions` file, as a
+/// version query
+fn parse_tool_versions(content: &str) -> Result<Option<String>> {
+    let Some(version) = content.lines().find_map(|line| {
+        let mut fields = line.split('#').next()?.split_whitespace();
+        (fields.next()? == "java").then(|| fields.next())?
+    }) else {
+        return Ok(None);
+    };
+
+    // asdf-java versions are "<distribution>-<version>"
+    if let Some(version) = version.strip_prefix("graalvm-community-") {
+        return Ok(Some(format!("graalvm ce@{}", version)));
+    }
+    Ok(Some(match version.split_once('-') {
+        Some(("openjdk", version)) => version.to_string(),
+        Some(("adoptopenjdk", version)) => format!("temurin@{}", version),
+        Some(("corretto", version)) => format!("corretto@{}", corretto_version(version)),
+        Some(("zulu", version)) if is_zulu_version(version) => anyhow::bail!(
+            "'zulu-{}' is an Azul Zulu version number, which jaman cannot map to a Java version; \
+             pin the Java version in a .java-version file instead, e.g. 'zulu@21.0.2'",
+            version
+        ),
+        Some((vendor, version)) if vendor.chars().all(|c| c.is_ascii_alphabetic()) => {
+            format!("{}@{}", vendor, version)
+        }
+        _ => version.to_string(),
+    }))
+}
+
+/// Corretto's own "21.0.2.13.1" and "8.402.08.1" as Java versions; plain
+/// Java versions such as "21.0.2" are kept
+fn corretto_version(version: &str) -> String {
+    let parts = version.split('.').count();
+    if parts >= 5 || (parts == 4 && version.starts_with("8.")) {
+        CorrettoProvider::normalize_version(version)
+    } else {
+        version.to_string()
+    }
+}
+
+/// Whether an asdf Zulu version is Azul's product version ("21.32.17" for
+/// Java 21.0.2) rather than a Java version. Java's interim number is always
+/// 0, Zulu's second component never is.
+fn is_zulu_version(version: &str) -> bool {
+    version
+        .split(['.', '+'])
+        .nth(1)
+        .and_then(|interim| interim.parse::<u32>().ok())
+        .is_some_and(|interim| interim > 0)
+}
@@ -0,0 +1,168 @@
+use super::{Provider, Release, Target, is_lts_release};
+use crate::config::{Channel, ImageType};
+use crate::downloader::AvailableVersion;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::sync::OnceCell;
+
+const DEFAULT_INDEX_URL: &str =
+    "https://corretto.github.io/corretto-downloads/latest_links/indexmap_with_checksum.json";
+const DEFAULT_DOWNLOAD_BASE: &str = "https://corretto.aws";
+
+/// Amazon Corretto builds listed in Corretto's published checksum index
+pub struct CorrettoProvider {
+    index_url: String,
+    download_base: String,
+    index: OnceCell<CorrettoIndex>,
+}
+
+impl Default for CorrettoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CorrettoProvider {
+    pub fn new() -> Self {
+        Self::with_urls(DEFAULT_INDEX_URL, DEFAULT_DOWNLOAD_BASE)
+    }
+
+    /// Read the index from `index_url` and resolve its resource paths against
+    /// `download_base`
+    pub fn with_urls(index_url: &str, download_base: &str) -> Self {
+        Self {
+            index_url: index_url.to_string(),
+            download_base: download_base.trim_end_matches('/').to_string(),
+            index: OnceCell::new(),
+        }
+    }
+
+    async fn index(&self, client: &Client) -> Result<&CorrettoIndex> {
+        self.index
+            .get_or_try_init(|| async {
+                let index: CorrettoIndex = client
+                    .get(&self.index_url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                Ok(index)
+            })
+            .await
+    }
+
+    /// Turn Corretto's "21.0.2.13.1" / "8.402.08.1" into "21.0.2+13" / "8.0.402+8"
+    pub fn normalize_version(corretto_version: &str) -> String {
+        let parts: Vec<u32> = corretto_version
+            .split('.')
+            .map_while(|p| p.parse().ok())
+            .collect();
+
+        match parts.as_slice() {
+            [8, update, build, ..] => format!("8.0.{}+{}", update, build),
+            [feature, interim, update, build, ..] => {
+                format!("{}.{}.{}+{}", feature, interim, update, build)
+            }
+            _ => corretto_version.to_string(),
+        }
+    }
+
+    /// Extract the Corretto version from a resource path such as
+    /// "/downloads/resources/21.0.2.13.1/amazon-corretto-21.0.2.13.1-linux-x64.tar.gz"
+    fn version_from_resource(resource: &str) -> Option<&str> {
+        resource
+            .split('/')
+            .skip_while(|segment| *segment != "resources")
+            .nth(1)
+    }
+}
+
+#[async_trait]
+impl Provider for CorrettoProvider {
+    fn vendor(&self) -> &str {
+        "Amazon Corretto"
+    }
+
+    async fn list_releases(&self, client: &Client) -> Result<Vec<Release>> {
+        let index = self.index(client).await?;
+
+        let mut majors: Vec<u32> = index
+            .values()
+            .flat_map(|archs| archs.values())
+            .filter_map(|images| images.get("jdk"))
+            .flat_map(|releases| releases.keys())
+            .filter_map(|major| major.parse().ok())
+            .collect();
+        majors.sort_unstable();
+        majors.dedup();
+
+        Ok(majors
+            .into_iter()
+            .map(|major| Release {
+                major,
+                is_lts: is_lts_release(major),
+            })
+            .collect())
+    }
+
+    async fn resolve_assets(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        let index = self.index(client).await?;
+
+        let os = match target.os.as_str() {
+            "mac" => "macos",
+            other => other,
+        };
+        let extension = if os == "windows" { "zip" } else { "tar.gz" };
+        let image_type = match target.image_type {
+            ImageType::Jdk => "jdk",
+            ImageType::Jre => "jre",
+            ImageType::Headless | ImageType::Debug => return Ok(Vec::new()),
+        };
+
+        let Some(package) = index
+            .get(os)
+            .and_then(|archs| archs.get(&target.arch))
+            .and_then(|images| images.get(image_type))
+            .and_then(|releases| releases.get(&release.major.to_string()))
+            .and_then(|packages| packages.get(extension))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let corretto_version = Self::version_from_resource(&package.resource)
+            .ok_or_else(|| anyhow::anyhow!("Unexpected resource path: {}", package.resource))?;
+
+        Ok(vec![AvailableVersion {
+            version: Self::normalize_version(corretto_version),
+            vendor: self.vendor().to_string(),
+            is_lts: release.is_lts,
+            architecture: target.arch.clone(),
+            download_url: format!("{}{}", self.download_base, package.resource),
+            checksum: package.checksum_sha256.clone(),
+            javafx_bundled: false,
+            image_type: target.image_type,
+            channel: Channel::Ga,
+        }])
+    }
+}
+
+// Corretto index structure: os -> arch -> image type -> major -> extension -> package
+type CorrettoIndex = HashMap<
+    String,
+    HashMap<String, HashMap<String, HashMap<String, HashMap<String, CorrettoPackage>>>>,
+>;
+
+#[derive(Debug, Deserialize)]
+struct CorrettoPackage {
+    resource: String,
+    checksum_sha256: Option<String>,
+}
@@ -0,0 +1,289 @@
+use super::{Provider, Release, Target, is_lts_release};
+use crate::config::{Channel, ImageType};
+use crate::downloader::AvailableVersion;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://api.foojay.io";
+
+/// Distributions queried when none are configured explicitly
+const DEFAULT_DISTRIBUTIONS: &[&str] = &[
+    "temurin",
+    "zulu",
+    "liberica",
+    "corretto",
+    "sap_machine",
+    "semeru",
+    "dragonwell",
+    "microsoft",
+    "graalvm_community",
+    "graalvm",
+];
+
+/// Aggregate backend over the foojay Discovery API, covering many vendors at once
+pub struct FoojayProvider {
+    base_url: String,
+    distributions: Vec<String>,
+}
+
+impl Default for FoojayProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FoojayProvider {
+    pub fn new() -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL)
+    }
+
+    /// Point the provider at another Disco API endpoint
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            distributions: DEFAULT_DISTRIBUTIONS
+                .iter()
+                .map(|d| d.to_string())
+                .collect(),
+        }
+    }
+
+    /// Only query the given foojay distribution ids (e.g. "temurin", "liberica")
+    #[allow(dead_code)]
+    pub fn with_distributions(mut self, distributions: &[&str]) -> Self {
+        self.distributions = distributions.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    /// Human-readable vendor name for a foojay distribution id, matching the
+    /// names the direct providers use
+    pub fn vendor_name(distribution: &str) -> String {
+        match distribution {
+            "temurin" => "Eclipse Temurin",
+            "zulu" => "Azul Zulu",
+            "liberica" => "BellSoft Liberica",
+            "corretto" => "Amazon Corretto",
+            "sap_machine" => "SapMachine",
+            "semeru" => "IBM Semeru",
+            "dragonwell" => "Alibaba Dragonwell",
+            "microsoft" => "Microsoft",
+            "graalvm_community" => "GraalVM CE",
+            "graalvm" => "Oracle GraalVM",
+            other => return other.to_string(),
+        }
+        .to_string()
+    }
+
+    /// Latest packages of a feature release on the given channel
+    async fn fetch_packages(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+        channel: Channel,
+    ) -> Result<Vec<AvailableVersion>> {
+        let os = match target.os.as_str() {
+            "mac" => "macos",
+            other => other,
+        };
+        let archive_type = if os == "windows" { "zip" } else { "tar.gz" };
+        let package_type = match target.image_type {
+            ImageType::Jdk => "jdk",
+            ImageType::Jre => "jre",
+            ImageType::Headless | ImageType::Debug => return Ok(Vec::new()),
+        };
+
+        let mut query: Vec<(&str, String)> = vec![
+            ("version", release.major.to_string()),
+            ("operating_system", os.to_string()),
+            ("architecture", target.arch.clone()),
+            ("archive_type", archive_type.to_string()),
+            ("package_type", package_type.to_string()),
+            ("release_status", channel.to_string()),
+            ("latest", "available".to_string()),
+        ];
+        if os == "linux" {
+            query.push(("lib_c_type", "glibc".to_string()));
+        }
+        for distribution in &self.distributions {
+            query.push(("distribution", distribution.clone()));
+        }
+
+        let url = format!("{}/disco/v3.0/packages", self.base_url);
+        let response: FoojayResponse<FoojayPackage> = client
+            .get(&url)
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .filter(|package| package.directly_downloadable)
+            .map(|package| AvailableVersion {
+                version: package.java_version,
+                vendor: Self::vendor_name(&package.distribution),
+                is_lts: release.is_lts,
+                architecture: target.arch.clone(),
+                download_url: package.links.pkg_download_redirect,
+                checksum: None,
+                javafx_bundled: package.javafx_bundled,
+                image_type: target.image_type,
+                channel,
+            })
+            .collect())
+    }
+
+    /// Package id from a "<base>/disco/v3.0/ids/<id>/redirect" download link
+    fn package_id(&self, download_url: &str) -> Option<String> {
+        download_url
+            .strip_prefix(&format!("{}/disco/v3.0/ids/", self.base_url))?
+            .strip_suffix("/redirect")
+            .map(|id| id.to_string())
+    }
+}
+
+#[async_trait]
+impl Provider for FoojayProvider {
+    fn vendor(&self) -> &str {
+        "foojay"
+    }
+
+    fn provides(&self, version: &AvailableVersion) -> bool {
+        self.package_id(&version.download_url).is_some()
+    }
+
+    async fn list_releases(&self, client: &Client) -> Result<Vec<Release>> {
+        let url = format!("{}/disco/v3.0/major_versions", self.base_url);
+        let response: FoojayResponse<FoojayMajorVersion> = client
+            .get(&url)
+            .query(&[("ea", "false"), ("ga", "true"), ("maintained", "true")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut releases: Vec<Release> = response
+            .result
+            .into_iter()
+            .map(|major| Release {
+                major: major.major_version,
+                is_lts: match major.term_of_support.as_deref() {
+                    Some(term) => term.eq_ignore_ascii_case("lts"),
+                    None => is_lts_release(major.major_version),
+                },
+            })
+            .collect();
+        releases.sort_by_key(|r| r.major);
+
+        Ok(releases)
+    }
+
+    async fn resolve_assets(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        self.fetch_packages(client, release, target, Channel::Ga)
+            .await
+    }
+
+    async fn list_early_access(
+        &self,
+        client: &Client,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        let url = format!("{}/disco/v3.0/major_versions", self.base_url);
+        let response: FoojayResponse<FoojayMajorVersion> = client
+            .get(&url)
+            .query(&[("ea", "true"), ("ga", "false"), ("maintained", "true")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut versions = Vec::new();
+        for major in response.result {
+            let release = Release {
+                major: major.major_version,
+                is_lts: is_lts_release(major.major_version),
+            };
+            versions.extend(
+                self.fetch_packages(client, &release, target, Channel::Ea)
+                    .await?,
+            );
+        }
+
+        Ok(versions)
+    }
+
+    async fn fetch_checksum(
+        &self,
+        client: &Client,
+        version: &AvailableVersion,
+    ) -> Result<Option<String>> {
+        let Some(id) = self.package_id(&version.download_url) else {
+            return Ok(version.checksum.clone());
+        };
+
+        let url = format!("{}/disco/v3.0/ids/{}", self.base_url, id);
+        let response: FoojayResponse<FoojayPackageInfo> = client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .find(|info| info.checksum_type.eq_ignore_ascii_case("sha256"))
+            .map(|info| info.checksum)
+            .filter(|checksum| !checksum.is_empty()))
+    }
+}
+
+// Disco API structures
+#[derive(Debug, Deserialize)]
+struct FoojayResponse<T> {
+    result: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FoojayMajorVersion {
+    major_version: u32,
+    term_of_support: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FoojayPackage {
+    distribution: String,
+    java_version: String,
+    #[serde(default)]
+    javafx_bundled: bool,
+    #[serde(default)]
+    directly_downloadable: bool,
+    links: FoojayLinks,
+}
+
+#[derive(Debug, Deserialize)]
+struct FoojayLinks {
+    pkg_download_redirect: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FoojayPackageInfo {
+    #[serde(default)]
+    checksum: String,
+    #[serde(default)]
+    checksum_type: String,
+}
@@ -0,0 +1,283 @@
+use super::{Provider, Release, Target, is_lts_release};
+use crate::config::{Channel, ImageType};
+use crate::downloader::AvailableVersion;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+
+const CE_RELEASES_URL: &str = "https://api.github.com/repos/graalvm/graalvm-ce-builds/releases";
+const ORACLE_DOWNLOAD_BASE: &str = "https://download.oracle.com/graalvm";
+
+/// Feature releases Oracle publishes a "latest" GraalVM download for
+const ORACLE_RELEASES: &[u32] = &[17, 21, 25];
+
+/// GraalVM uses "macos" where the rest of jaman says "mac"
+fn graalvm_os(target: &Target) -> &str {
+    match target.os.as_str() {
+        "mac" => "macos",
+        other => other,
+    }
+}
+
+fn graalvm_extension(target: &Target) -> &'static str {
+    if target.os == "windows" {
+        "zip"
+    } else {
+        "tar.gz"
+    }
+}
+
+/// Read the `<archive>.sha256` file GraalVM publishes next to each archive
+async fn fetch_sidecar_checksum(client: &Client, download_url: &str) -> Result<Option<String>> {
+    let body = client
+        .get(format!("{}.sha256", download_url))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    Ok(body.split_whitespace().next().map(|hash| hash.to_string()))
+}
+
+/// GraalVM Community Edition builds published on GitHub
+pub struct GraalVmCeProvider {
+    releases_url: String,
+    releases: OnceCell<Vec<GitHubRelease>>,
+}
+
+impl Default for GraalVmCeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraalVmCeProvider {
+    pub fn new() -> Self {
+        Self::with_releases_url(CE_RELEASES_URL)
+    }
+
+    /// Read releases from another GitHub-compatible releases endpoint
+    pub fn with_releases_url(releases_url: &str) -> Self {
+        Self {
+            releases_url: releases_url.to_string(),
+            releases: OnceCell::new(),
+        }
+    }
+
+    async fn releases(&self, client: &Client) -> Result<&Vec<GitHubRelease>> {
+        self.releases
+            .get_or_try_init(|| async {
+                let releases: Vec<GitHubRelease> = client
+                    .get(&self.releases_url)
+                    .query(&[("per_page", "100")])
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+
+                // Only JDK-versioned releases ("jdk-21.0.2"); older "vm-22.3.x" tags
+                // used a different naming scheme
+                Ok(releases
+                    .into_iter()
+                    .filter(|r| !r.prerelease && r.jdk_version().is_some())
+                    .collect())
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl Provider for GraalVmCeProvider {
+    fn vendor(&self) -> &str {
+        "GraalVM CE"
+    }
+
+    async fn list_releases(&self, client: &Client) -> Result<Vec<Release>> {
+        let mut majors: Vec<u32> = self
+            .releases(client)
+            .await?
+            .iter()
+            .filter_map(|r| r.major())
+            .collect();
+        majors.sort_unstable();
+        majors.dedup();
+
+        Ok(majors
+            .into_iter()
+            .map(|major| Release {
+                major,
+                is_lts: is_lts_release(major),
+            })
+            .collect())
+    }
+
+    async fn resolve_assets(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        // GraalVM is only published as a full JDK
+        if target.image_type != ImageType::Jdk {
+            return Ok(Vec::new());
+        }
+
+        // GitHub lists releases newest first
+        let Some(latest) = self
+            .releases(client)
+            .await?
+            .iter()
+            .find(|r| r.major() == Some(release.major))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let version = latest.jdk_version().unwrap_or_default();
+        let asset_name = format!(
+            "graalvm-community-jdk-{}_{}-{}_bin.{}",
+            version,
+            graalvm_os(target),
+            target.arch,
+            graalvm_extension(target)
+        );
+
+        Ok(latest
+            .assets
+            .iter()
+            .filter(|asset| asset.name == asset_name)
+            .map(|asset| AvailableVersion {
+                version: version.to_string(),
+                vendor: self.vendor().to_string(),
+                is_lts: release.is_lts,
+                architecture: target.arch.clone(),
+                download_url: asset.browser_download_url.clone(),
+                checksum: None,
+                javafx_bundled: false,
+                image_type: target.image_type,
+                channel: Channel::Ga,
+            })
+            .collect())
+    }
+
+    async fn fetch_checksum(
+        &self,
+        client: &Client,
+        version: &AvailableVersion,
+    ) -> Result<Option<String>> {
+        fetch_sidecar_checksum(client, &version.download_url).await
+    }
+}
+
+/// Oracle GraalVM builds from Oracle's "latest" download links
+pub struct OracleGraalVmProvider {
+    download_base: String,
+    releases: Vec<u32>,
+}
+
+impl Default for OracleGraalVmProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OracleGraalVmProvider {
+    pub fn new() -> Self {
+        Self::with_download_base(ORACLE_DOWNLOAD_BASE)
+    }
+
+    /// Resolve downloads against another base URL (e.g. a mirror)
+    pub fn with_download_base(download_base: &str) -> Self {
+        Self {
+            download_base: download_base.trim_end_matches('/').to_string(),
+            releases: ORACLE_RELEASES.to_vec(),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for OracleGraalVmProvider {
+    fn vendor(&self) -> &str {
+        "Oracle GraalVM"
+    }
+
+    async fn list_releases(&self, _client: &Client) -> Result<Vec<Release>> {
+        Ok(self
+            .releases
+            .iter()
+            .map(|&major| Release {
+                major,
+                is_lts: is_lts_release(major),
+            })
+            .collect())
+    }
+
+    async fn resolve_assets(
+        &self,
+        _client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        if target.image_type != ImageType::Jdk {
+            return Ok(Vec::new());
+        }
+
+        // The "latest" links do not carry the update number, so versions are
+        // listed by feature release only
+        Ok(vec![AvailableVersion {
+            version: release.major.to_string(),
+            vendor: self.vendor().to_string(),
+            is_lts: release.is_lts,
+            architecture: target.arch.clone(),
+            download_url: format!(
+                "{}/{}/latest/graalvm-jdk-{}_{}-{}_bin.{}",
+                self.download_base,
+                release.major,
+                release.major,
+                graalvm_os(target),
+                target.arch,
+                graalvm_extension(target)
+            ),
+            checksum: None,
+            javafx_bundled: false,
+            image_type: target.image_type,
+            channel: Channel::Ga,
+        }])
+    }
+
+    async fn fetch_checksum(
+        &self,
+        client: &Client,
+        version: &AvailableVersion,
+    ) -> Result<Option<String>> {
+        fetch_sidecar_checksum(client, &version.download_url).await
+    }
+}
+
+// GitHub API structures
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    #[serde(default)]
+    prerelease: bool,
+    assets: Vec<GitHubAsset>,
+}
+
+impl GitHubRelease {
+    fn jdk_version(&self) -> Option<&str> {
+        self.tag_name.strip_prefix("jdk-")
+    }
+
+    fn major(&self) -> Option<u32> {
+        self.jdk_version()?.split('.').next()?.parse().ok()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubAsset {
+    name: String,
+    browser_download_url: String,
+}
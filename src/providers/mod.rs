@@ -0,0 +1,138 @@
+//! Distribution providers that feed `AvailableVersion`s into the `Downloader`
+
+pub mod corretto;
+pub mod foojay;
+pub mod graalvm;
+pub mod temurin;
+pub mod zulu;
+
+use crate::config::ImageType;
+use crate::downloader::AvailableVersion;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+
+pub use corretto::CorrettoProvider;
+pub use foojay::FoojayProvider;
+pub use graalvm::{GraalVmCeProvider, OracleGraalVmProvider};
+pub use temurin::TemurinProvider;
+pub use zulu::ZuluProvider;
+
+/// Operating system, architecture and image type that assets are resolved for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    /// One of "windows", "mac" or "linux"
+    pub os: String,
+    /// One of "x64" or "aarch64"
+    pub arch: String,
+    pub image_type: ImageType,
+}
+
+impl Target {
+    pub fn new(os: &str, arch: &str) -> Self {
+        Self {
+            os: os.to_string(),
+            arch: arch.to_string(),
+            image_type: ImageType::Jdk,
+        }
+    }
+
+    /// Resolve another image type than the full JDK
+    #[allow(dead_code)]
+    pub fn with_image_type(mut self, image_type: ImageType) -> Self {
+        self.image_type = image_type;
+        self
+    }
+
+    /// Target matching the machine jaman is running on
+    pub fn host() -> Self {
+        let os = if cfg!(windows) {
+            "windows"
+        } else if cfg!(target_os = "macos") {
+            "mac"
+        } else {
+            "linux"
+        };
+
+        let arch = match std::env::consts::ARCH {
+            "x86_64" => "x64",
+            "aarch64" => "aarch64",
+            _ => "x64",
+        };
+
+        Self::new(os, arch)
+    }
+}
+
+impl Default for Target {
+    fn default() -> Self {
+        Self::host()
+    }
+}
+
+/// A feature release (e.g. 21) offered by a provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub major: u32,
+    pub is_lts: bool,
+}
+
+/// Whether a feature release is a long-term support line (8, 11, then every
+/// fourth release starting with 17)
+pub fn is_lts_release(major: u32) -> bool {
+    major == 8 || major == 11 || (major >= 17 && (major - 17).is_multiple_of(4))
+}
+
+/// A source of installable Java distributions
+#[async_trait]
+pub trait Provider: Send + Sync {
+    /// Vendor name recorded on every version this provider yields
+    fn vendor(&self) -> &str;
+
+    /// Whether a version was yielded by this provider, so checksum lookups reach
+    /// the right source
+    fn provides(&self, version: &AvailableVersion) -> bool {
+        version.vendor == self.vendor()
+    }
+
+    /// List the feature releases this provider offers
+    async fn list_releases(&self, client: &Client) -> Result<Vec<Release>>;
+
+    /// Resolve the downloadable assets of a release for the given target
+    async fn resolve_assets(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>>;
+
+    /// List every GA build of a release, newest first. Providers without a
+    /// release history only offer the latest build.
+    async fn list_patch_releases(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        self.resolve_assets(client, release, target).await
+    }
+
+    /// List the latest early-access build of each upcoming feature release.
+    /// Providers that publish no EA builds offer none.
+    async fn list_early_access(
+        &self,
+        _client: &Client,
+        _target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch the SHA-256 checksum of an asset whose listing did not include one
+    async fn fetch_checksum(
+        &self,
+        _client: &Client,
+        version: &AvailableVersion,
+    ) -> Result<Option<String>> {
+        Ok(version.checksum.clone())
+    }
+}
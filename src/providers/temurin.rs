@@ -0,0 +1,271 @@
+use super::{Provider, Release, Target, is_lts_release};
+use crate::config::{Channel, ImageType};
+use crate::downloader::AvailableVersion;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://api.adoptium.net";
+const PAGE_SIZE: usize = 20;
+
+/// Eclipse Temurin builds published through the Adoptium API
+pub struct TemurinProvider {
+    base_url: String,
+}
+
+impl Default for TemurinProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemurinProvider {
+    pub fn new() -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL)
+    }
+
+    /// Point the provider at another Adoptium-compatible API (e.g. a mirror)
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn available_releases(&self, client: &Client) -> Result<AdoptiumAvailableReleases> {
+        let url = format!("{}/v3/info/available_releases", self.base_url);
+        Ok(client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Adoptium's name for an image type; Temurin has no headless images
+    fn image_type(image_type: ImageType) -> Option<&'static str> {
+        match image_type {
+            ImageType::Jdk => Some("jdk"),
+            ImageType::Jre => Some("jre"),
+            ImageType::Debug => Some("debugimage"),
+            ImageType::Headless => None,
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for TemurinProvider {
+    fn vendor(&self) -> &str {
+        "Eclipse Temurin"
+    }
+
+    async fn list_releases(&self, client: &Client) -> Result<Vec<Release>> {
+        let info = self.available_releases(client).await?;
+
+        Ok(info
+            .available_releases
+            .iter()
+            .map(|&major| Release {
+                major,
+                is_lts: info.available_lts_releases.contains(&major),
+            })
+            .collect())
+    }
+
+    async fn resolve_assets(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        let Some(image_type) = Self::image_type(target.image_type) else {
+            return Ok(Vec::new());
+        };
+
+        let url = format!(
+            "{}/v3/assets/latest/{}/hotspot?architecture={}&image_type={}&os={}",
+            self.base_url, release.major, target.arch, image_type, target.os
+        );
+
+        let assets: Vec<AdoptiumAsset> = client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(assets
+            .into_iter()
+            .map(|asset| AvailableVersion {
+                version: asset.version.semver,
+                vendor: self.vendor().to_string(),
+                is_lts: release.is_lts,
+                architecture: target.arch.clone(),
+                download_url: asset.binary.package.link,
+                checksum: Some(asset.binary.package.checksum),
+                javafx_bundled: false,
+                image_type: target.image_type,
+                channel: Channel::Ga,
+            })
+            .collect())
+    }
+
+    async fn list_patch_releases(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        let Some(image_type) = Self::image_type(target.image_type) else {
+            return Ok(Vec::new());
+        };
+
+        let url = format!(
+            "{}/v3/assets/feature_releases/{}/ga",
+            self.base_url, release.major
+        );
+        let mut versions = Vec::new();
+
+        for page in 0.. {
+            let response = client
+                .get(&url)
+                .query(&[
+                    ("architecture", target.arch.as_str()),
+                    ("image_type", image_type),
+                    ("jvm_impl", "hotspot"),
+                    ("os", target.os.as_str()),
+                    ("sort_order", "DESC"),
+                    ("vendor", "eclipse"),
+                ])
+                .query(&[("page", page), ("page_size", PAGE_SIZE)])
+                .send()
+                .await?;
+
+            // The API answers 404 once the pages run out
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                break;
+            }
+
+            let releases: Vec<AdoptiumRelease> = response.error_for_status()?.json().await?;
+            let page_len = releases.len();
+
+            for adoptium_release in releases {
+                if let Some(binary) = adoptium_release.binaries.into_iter().next() {
+                    versions.push(AvailableVersion {
+                        version: adoptium_release.version_data.semver,
+                        vendor: self.vendor().to_string(),
+                        is_lts: release.is_lts,
+                        architecture: target.arch.clone(),
+                        download_url: binary.package.link,
+                        checksum: Some(binary.package.checksum),
+                        javafx_bundled: false,
+                        image_type: target.image_type,
+                        channel: Channel::Ga,
+                    });
+                }
+            }
+
+            if page_len < PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(versions)
+    }
+
+    async fn list_early_access(
+        &self,
+        client: &Client,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        let Some(image_type) = Self::image_type(target.image_type) else {
+            return Ok(Vec::new());
+        };
+
+        // Feature releases past the newest GA one only have EA builds
+        let info = self.available_releases(client).await?;
+        let mut versions = Vec::new();
+
+        for major in (info.most_recent_feature_release + 1)..=info.tip_version {
+            let url = format!("{}/v3/assets/feature_releases/{}/ea", self.base_url, major);
+            let response = client
+                .get(&url)
+                .query(&[
+                    ("architecture", target.arch.as_str()),
+                    ("image_type", image_type),
+                    ("jvm_impl", "hotspot"),
+                    ("os", target.os.as_str()),
+                    ("page_size", "1"),
+                    ("sort_order", "DESC"),
+                    ("vendor", "eclipse"),
+                ])
+                .send()
+                .await?;
+
+            // No EA build published yet for this platform
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                continue;
+            }
+
+            let releases: Vec<AdoptiumRelease> = response.error_for_status()?.json().await?;
+            for adoptium_release in releases {
+                if let Some(binary) = adoptium_release.binaries.into_iter().next() {
+                    versions.push(AvailableVersion {
+                        version: adoptium_release.version_data.semver,
+                        vendor: self.vendor().to_string(),
+                        is_lts: is_lts_release(major),
+                        architecture: target.arch.clone(),
+                        download_url: binary.package.link,
+                        checksum: Some(binary.package.checksum),
+                        javafx_bundled: false,
+                        image_type: target.image_type,
+                        channel: Channel::Ea,
+                    });
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+// Adoptium API structures
+#[derive(Debug, Deserialize)]
+struct AdoptiumAvailableReleases {
+    available_releases: Vec<u32>,
+    available_lts_releases: Vec<u32>,
+    #[serde(default)]
+    most_recent_feature_release: u32,
+    #[serde(default)]
+    tip_version: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumAsset {
+    binary: AdoptiumBinary,
+    version: AdoptiumVersion,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumBinary {
+    package: AdoptiumPackage,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumPackage {
+    link: String,
+    checksum: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumVersion {
+    semver: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumRelease {
+    binaries: Vec<AdoptiumBinary>,
+    version_data: AdoptiumVersion,
+}
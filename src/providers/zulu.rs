@@ -0,0 +1,186 @@
+use super::{Provider, Release, Target, is_lts_release};
+use crate::config::{Channel, ImageType};
+use crate::downloader::AvailableVersion;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://api.azul.com";
+
+/// Azul Zulu builds from the Azul metadata API, including JavaFX ("fx") bundles
+pub struct ZuluProvider {
+    base_url: String,
+}
+
+impl Default for ZuluProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZuluProvider {
+    pub fn new() -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL)
+    }
+
+    /// Point the provider at another Azul metadata API endpoint
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn fetch_packages(
+        &self,
+        client: &Client,
+        params: &[(&str, String)],
+    ) -> Result<Vec<ZuluPackage>> {
+        let url = format!("{}/metadata/v1/zulu/packages/", self.base_url);
+        let packages = client
+            .get(&url)
+            .query(&[
+                ("release_status", "ga"),
+                ("availability_types", "CA"),
+                ("latest", "true"),
+                ("page_size", "1000"),
+                (
+                    "include_fields",
+                    "sha256_hash,javafx_bundled,support_term,java_package_features",
+                ),
+            ])
+            .query(params)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(packages)
+    }
+}
+
+#[async_trait]
+impl Provider for ZuluProvider {
+    fn vendor(&self) -> &str {
+        "Azul Zulu"
+    }
+
+    async fn list_releases(&self, client: &Client) -> Result<Vec<Release>> {
+        let packages = self
+            .fetch_packages(client, &[("java_package_type", "jdk".to_string())])
+            .await?;
+
+        let mut releases: Vec<Release> = Vec::new();
+        for package in packages {
+            let Some(&major) = package.java_version.first() else {
+                continue;
+            };
+            if releases.iter().any(|r| r.major == major) {
+                continue;
+            }
+
+            let is_lts = match package.support_term.as_deref() {
+                Some(term) => term == "lts",
+                None => is_lts_release(major),
+            };
+            releases.push(Release { major, is_lts });
+        }
+        releases.sort_by_key(|r| r.major);
+
+        Ok(releases)
+    }
+
+    async fn resolve_assets(
+        &self,
+        client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> Result<Vec<AvailableVersion>> {
+        // Plain "linux" also matches musl (Alpine) builds
+        let os = match target.os.as_str() {
+            "mac" => "macos",
+            "linux" => "linux_glibc",
+            other => other,
+        };
+        let archive_type = if target.os == "windows" {
+            "zip"
+        } else {
+            "tar.gz"
+        };
+        let (package_type, features) = match target.image_type {
+            ImageType::Jdk => ("jdk", None),
+            ImageType::Jre => ("jre", None),
+            ImageType::Headless => ("jdk", Some("headless")),
+            ImageType::Debug => return Ok(Vec::new()),
+        };
+
+        let mut versions = Vec::new();
+
+        // Plain and JavaFX-bundled packages are listed separately so the flag is
+        // always known, even if the API omits it from the response
+        for javafx_bundled in [false, true] {
+            let mut params = vec![
+                ("java_version", release.major.to_string()),
+                ("os", os.to_string()),
+                ("arch", target.arch.clone()),
+                ("archive_type", archive_type.to_string()),
+                ("java_package_type", package_type.to_string()),
+                ("javafx_bundled", javafx_bundled.to_string()),
+                ("crac_supported", "false".to_string()),
+            ];
+            if let Some(features) = features {
+                params.push(("java_package_features", features.to_string()));
+            }
+
+            let packages = self.fetch_packages(client, &params).await?;
+
+            // The API has no way to exclude a feature, so headless packages
+            // are dropped here unless they were asked for
+            let packages = packages.into_iter().filter(|package| {
+                features.is_some()
+                    || !package
+                        .java_package_features
+                        .iter()
+                        .any(|f| f == "headless")
+            });
+
+            versions.extend(packages.map(|package| {
+                let version = match package.java_version.as_slice() {
+                    [feature, interim, update, ..] => format!(
+                        "{}.{}.{}+{}",
+                        feature, interim, update, package.openjdk_build_number
+                    ),
+                    _ => release.major.to_string(),
+                };
+
+                AvailableVersion {
+                    version,
+                    vendor: self.vendor().to_string(),
+                    is_lts: release.is_lts,
+                    architecture: target.arch.clone(),
+                    download_url: package.download_url,
+                    checksum: package.sha256_hash,
+                    javafx_bundled: package.javafx_bundled.unwrap_or(javafx_bundled),
+                    image_type: target.image_type,
+                    channel: Channel::Ga,
+                }
+            }));
+        }
+
+        Ok(versions)
+    }
+}
+
+// Azul metadata API structures
+#[derive(Debug, Deserialize)]
+struct ZuluPackage {
+    java_version: Vec<u32>,
+    #[serde(default)]
+    openjdk_build_number: u32,
+    download_url: String,
+    sha256_hash: Option<String>,
+    javafx_bundled: Option<bool>,
+    support_term: Option<String>,
+    #[serde(default)]
+    java_package_features: Vec<String>,
+}
@@ -0,0 +1,308 @@
+//! Version queries that pick installed and available versions: "21",
+//! "21.0.2+13", ">=17 <21", "lts", "latest", "temurin@17" or "corretto-11"
+
+use crate::config::JavaVersion;
+use crate::downloader::AvailableVersion;
+use crate::version::Version;
+use anyhow::Result;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A version a query can select, either installed or available for download
+pub trait Candidate {
+    fn version(&self) -> &str;
+    fn vendor(&self) -> &str;
+    fn is_lts(&self) -> bool;
+
+    /// One line naming the candidate in an ambiguity error
+    fn describe(&self) -> String {
+        format!("{} - {}", self.version(), self.vendor())
+    }
+
+    /// Whether the candidate can also be chosen by an installation ID
+    fn has_id(&self) -> bool {
+        false
+    }
+}
+
+impl Candidate for JavaVersion {
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn vendor(&self) -> &str {
+        &self.vendor
+    }
+
+    fn is_lts(&self) -> bool {
+        self.is_lts
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} - {} ({}) [{}]",
+            self.version,
+            self.vendor,
+            self.path.display(),
+            self.id
+        )
+    }
+
+    fn has_id(&self) -> bool {
+        true
+    }
+}
+
+impl Candidate for AvailableVersion {
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn vendor(&self) -> &str {
+        &self.vendor
+    }
+
+    fn is_lts(&self) -> bool {
+        self.is_lts
+    }
+}
+
+/// A parsed version query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionQuery {
+    /// Text the query was parsed from, for messages
+    text: String,
+    /// Matched case-insensitively against part of the vendor name
+    vendor: Option<String>,
+    selector: Selector,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    /// The newest version
+    Latest,
+    /// The newest LTS version
+    Lts,
+    /// Versions meeting every comparator
+    Range(Vec<Comparator>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    version: Version,
+    /// Number of `$VNUM` components written in the query; only those are
+    /// compared, so "17" covers every 17.x release
+    precision: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        let ordering = version.cmp_prefix(&self.version, self.precision);
+        match self.op {
+            Op::Eq => {
+                ordering.is_eq()
+                    && (self.version.pre.is_none() || version.pre == self.version.pre)
+                    && (self.version.build.is_none() || version.build == self.version.build)
+            }
+            Op::Gt => ordering.is_gt(),
+            Op::Ge => ordering.is_ge(),
+            Op::Lt => ordering.is_lt(),
+            Op::Le => ordering.is_le(),
+        }
+    }
+}
+
+impl VersionQuery {
+    pub fn matches(&self, candidate: &impl Candidate) -> bool {
+        let vendor_matches = self.vendor.as_ref().is_none_or(|vendor| {
+            candidate
+                .vendor()
+                .to_lowercase()
+                .contains(&vendor.to_lowercase())
+        });
+        if !vendor_matches {
+            return false;
+        }
+
+        match &self.selector {
+            Selector::Latest => true,
+            Selector::Lts => candidate.is_lts(),
+            Selector::Range(comparators) => match candidate.version().parse::<Version>() {
+                Ok(version) => comparators.iter().all(|c| c.matches(&version)),
+                Err(_) => false,
+            },
+        }
+    }
+
+    /// The newest matching candidate, or an error listing the candidates when
+    /// several share that version, e.g. the same release from two vendors
+    pub fn select<'a, C: Candidate + 'a>(
+        &self,
+        candidates: impl IntoIterator<Item = &'a C>,
+    ) -> Result<Option<&'a C>> {
+        let mut best: Vec<&C> = Vec::new();
+        for candidate in candidates.into_iter().filter(|c| self.matches(*c)) {
+            match best
+                .first()
+                .map(|b| crate::version::compare_ignore_optional(candidate.version(), b.version()))
+            {
+                None | Some(Ordering::Equal) => best.push(candidate),
+                Some(Ordering::Greater) => best = vec![candidate],
+                Some(Ordering::Less) => {}
+            }
+        }
+
+        if best.len() > 1 {
+            let list: Vec<_> = best.iter().map(|c| format!("  {}", c.describe())).collect();
+            let ids = if best[0].has_id() {
+                ", or use an ID shown by 'jaman list'"
+            } else {
+                ""
+            };
+            anyhow::bail!(
+                "'{}' is ambiguous, it matches:\n{}\nAdd a vendor to choose one, e.g. '<vendor>@{}'{}",
+                self.text,
+                list.join("\n"),
+                self.version_part(),
+                ids
+            );
+        }
+        Ok(best.pop())
+    }
+
+    /// Feature release the query is pinned to, e.g. 17 for "temurin@17.0.6"
+    /// but none for ">=17" or "lts"
+    pub fn feature(&self) -> Option<u32> {
+        match &self.selector {
+            Selector::Range(comparators) => match comparators.as_slice() {
+                [c] if c.op == Op::Eq => u32::try_from(c.version.feature()).ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether the query names one patch release or build ("17.0.6",
+    /// "21+35") rather than a whole feature release
+    pub fn is_patch(&self) -> bool {
+        match &self.selector {
+            Selector::Range(comparators) => match comparators.as_slice() {
+                [c] if c.op == Op::Eq => c.precision > 1 || c.version.build.is_some(),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// The query without its vendor qualifier
+    fn version_part(&self) -> &str {
+        match self.text.split_once('@') {
+            Some((_, version)) => version,
+            None => match split_vendor_prefix(&self.text) {
+                Some((_, version)) => version,
+                None => &self.text,
+            },
+        }
+    }
+}
+
+/// Split a legacy vendor-qualified query such as "corretto-21"
+fn split_vendor_prefix(query: &str) -> Option<(&str, &str)> {
+    query.split_once('-').filter(|(vendor, _)| {
+        // "jdk-21.0.2" is a version with the directory prefix, not a vendor
+        !vendor.is_empty()
+            && !vendor.eq_ignore_ascii_case("jdk")
+            && vendor.chars().all(|c| c.is_ascii_alphabetic())
+    })
+}
+
+impl FromStr for VersionQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let text = s.trim();
+        let (vendor, spec) = match text.split_once('@') {
+            Some((vendor, spec)) => (Some(vendor), spec),
+            None => match split_vendor_prefix(text) {
+                Some((vendor, spec)) => (Some(vendor), spec),
+                None => (None, text),
+            },
+        };
+        if vendor.is_some_and(|v| v.trim().is_empty()) {
+            anyhow::bail!("Missing vendor in version query '{}'", text);
+        }
+
+        let selector = match spec.trim().to_lowercase().as_str() {
+            "" => anyhow::bail!("Missing version in query '{}'", text),
+            "latest" => Selector::Latest,
+            "lts" => Selector::Lts,
+            _ => Selector::Range(parse_comparators(spec).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid version query '{}': {} (expected e.g. '21', '21.0.2+13', \
+                     '>=17 <21', 'lts', 'latest' or 'temurin@17')",
+                    text,
+                    e
+                )
+            })?),
+        };
+
+        Ok(Self {
+            text: text.to_string(),
+            vendor: vendor.map(|v| v.trim().to_string()),
+            selector,
+        })
+    }
+}
+
+/// Parse whitespace- or comma-separated comparators such as ">=17 <21"
+fn parse_comparators(spec: &str) -> Result<Vec<Comparator>> {
+    let mut comparators = Vec::new();
+    let mut tokens = spec.split([' ', ',']).filter(|t| !t.is_empty());
+
+    while let Some(token) = tokens.next() {
+        let (op, rest) = [
+            (">=", Op::Ge),
+            ("<=", Op::Le),
+            (">", Op::Gt),
+            ("<", Op::Lt),
+            ("=", Op::Eq),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| token.strip_prefix(prefix).map(|rest| (op, rest)))
+        .unwrap_or((Op::Eq, token));
+
+        // Allow a space between the operator and the version: ">= 17"
+        let text = if rest.is_empty() {
+            tokens
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("'{}' needs a version", token))?
+        } else {
+            rest
+        };
+        let (version, precision) = Version::parse_with_precision(text)?;
+        comparators.push(Comparator {
+            op,
+            version,
+            precision,
+        });
+    }
+
+    Ok(comparators)
+}
+
+impl fmt::Display for VersionQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
@@ -0,0 +1,98 @@
+//! Retries with exponential backoff for transient network failures
+
+use crate::config::NetworkSettings;
+use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// Upper bound for the delay between two attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A download received no data for longer than the read timeout
+#[derive(Debug, thiserror::Error)]
+#[error("no data received for {0:?}")]
+pub struct ReadTimeout(pub Duration);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts made after the first one failed
+    pub retries: u32,
+    /// Delay before the first retry
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_settings(&NetworkSettings::default())
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_settings(settings: &NetworkSettings) -> Self {
+        Self {
+            retries: settings.retries,
+            backoff: Duration::from_millis(settings.backoff_ms),
+        }
+    }
+
+    /// Policy that gives up after the first failure
+    #[allow(dead_code)]
+    pub fn none() -> Self {
+        Self {
+            retries: 0,
+            backoff: Duration::ZERO,
+        }
+    }
+
+    /// Delay before retry number `attempt` (starting at 0)
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_BACKOFF)
+    }
+
+    /// Run `operation`, retrying it while it fails with a transient error
+    pub async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match operation().await {
+                Err(e) if attempt < self.retries && is_transient(&e) => {
+                    tokio::time::sleep(self.backoff_for(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Whether an error is worth retrying: timeouts, dropped connections, rate
+/// limiting and server errors, but not client errors such as a 404
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if cause.is::<ReadTimeout>() {
+            return true;
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return match e.status() {
+                Some(status) => {
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                None => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body(),
+            };
+        }
+        cause.downcast_ref::<std::io::Error>().is_some_and(|e| {
+            matches!(
+                e.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::UnexpectedEof
+                    | std::io::ErrorKind::TimedOut
+            )
+        })
+    })
+}
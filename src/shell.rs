@@ -0,0 +1,352 @@
+//! Shell integration: prompt hooks that keep `JAVA_HOME` and `PATH` on the
+//! version in effect for the current directory, and the statements each
+//! shell (or a JSON consumer) needs to apply environment changes
+
+use anyhow::Result;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Records the JDK the hook put on `PATH`, so it can be taken off again
+pub const JAMAN_JAVA_HOME: &str = "JAMAN_JAVA_HOME";
+
+/// Set by the hook, telling jaman that the shell switches versions itself
+pub const JAMAN_SHELL: &str = "JAMAN_SHELL";
+
+/// Set by `jaman env`: the JDK chosen for this shell session, which the hook
+/// keeps instead of switching by directory until the variable is removed
+pub const JAMAN_SESSION_HOME: &str = "JAMAN_SESSION_HOME";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Pwsh,
+    Nu,
+    /// Windows `cmd.exe`; no prompt hook, `jaman env` only
+    Cmd,
+    /// Not a shell: a JSON object for scripts and editors
+    Json,
+}
+
+impl FromStr for Shell {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            "pwsh" | "powershell" => Ok(Self::Pwsh),
+            "nu" | "nushell" => Ok(Self::Nu),
+            "cmd" => Ok(Self::Cmd),
+            "json" => Ok(Self::Json),
+            other => anyhow::bail!(
+                "Unknown shell '{}' (expected 'bash', 'zsh', 'fish', 'pwsh', 'nu', 'cmd' or 'json')",
+                other
+            ),
+        }
+    }
+}
+
+impl Shell {
+    /// Name accepted by `--shell` and `jaman hook`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+            Self::Pwsh => "pwsh",
+            Self::Nu => "nu",
+            Self::Cmd => "cmd",
+            Self::Json => "json",
+        }
+    }
+
+    /// The shell jaman is running in: the one that loaded the hook, else the
+    /// login shell from `SHELL`, else PowerShell on Windows and bash elsewhere
+    pub fn detect() -> Self {
+        let from_var = |name: &str| {
+            let value = std::env::var_os(name)?;
+            let name = Path::new(&value)
+                .file_stem()?
+                .to_string_lossy()
+                .into_owned();
+            name.parse().ok()
+        };
+        from_var(JAMAN_SHELL)
+            .or_else(|| from_var("SHELL"))
+            .unwrap_or(if cfg!(windows) {
+                Self::Pwsh
+            } else {
+                Self::Bash
+            })
+    }
+}
+
+/// One environment variable to set, or to remove when `value` is `None`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvChange {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl EnvChange {
+    fn set(name: &str, value: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            value: Some(value.into()),
+        }
+    }
+
+    fn unset(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: None,
+        }
+    }
+}
+
+/// The environment the shell currently has, as far as switching JDKs goes
+#[derive(Debug, Clone, Default)]
+pub struct CurrentEnv {
+    pub path: Option<String>,
+    pub java_home: Option<String>,
+    /// The JDK jaman switched to last, from `JAMAN_JAVA_HOME`
+    pub jaman_java_home: Option<String>,
+    /// The JDK `jaman env` chose for the session, from `JAMAN_SESSION_HOME`
+    pub session_java_home: Option<String>,
+}
+
+impl CurrentEnv {
+    pub fn from_process() -> Self {
+        let var = |name: &str| std::env::var_os(name).map(|v| v.to_string_lossy().into_owned());
+        Self {
+            path: var("PATH"),
+            java_home: var("JAVA_HOME"),
+            jaman_java_home: var(JAMAN_JAVA_HOME),
+            session_java_home: var(JAMAN_SESSION_HOME),
+        }
+    }
+}
+
+/// Changes that move `JAVA_HOME` and `PATH` from the JDK jaman switched to
+/// last onto `java_home`, or off it when `java_home` is `None`. Nothing
+/// changes when the shell already uses `java_home`.
+pub fn java_home_changes(java_home: Option<&Path>, current: &CurrentEnv) -> Result<Vec<EnvChange>> {
+    let target = java_home.map(|home| home.to_string_lossy().into_owned());
+    if target == current.jaman_java_home && (target.is_none() || target == current.java_home) {
+        return Ok(Vec::new());
+    }
+
+    let mut changes = match java_home {
+        Some(home) => activation_changes(home, current)?,
+        None => {
+            let mut changes = Vec::new();
+            // Leave a JAVA_HOME alone that jaman did not set
+            if current.java_home.is_some() && current.java_home == current.jaman_java_home {
+                changes.push(EnvChange::unset("JAVA_HOME"));
+            }
+            if current.jaman_java_home.is_some() {
+                changes.push(EnvChange::unset(JAMAN_JAVA_HOME));
+            }
+            changes.push(EnvChange::set("PATH", path_with(None, current)?));
+            changes
+        }
+    };
+    changes.retain(|change| change.name != "PATH" || change.value != current.path);
+    Ok(changes)
+}
+
+/// Every change that puts `java_home` in effect: `JAVA_HOME`, `PATH` with the
+/// JDK's `bin` first and the one jaman switched to last removed, and
+/// `JAMAN_JAVA_HOME`. Unlike `java_home_changes` these are complete even when
+/// the shell already uses `java_home`, so they can be saved and applied later.
+pub fn activation_changes(java_home: &Path, current: &CurrentEnv) -> Result<Vec<EnvChange>> {
+    let home = java_home.to_string_lossy().into_owned();
+    Ok(vec![
+        EnvChange::set("JAVA_HOME", home.clone()),
+        EnvChange::set("PATH", path_with(Some(java_home), current)?),
+        EnvChange::set(JAMAN_JAVA_HOME, home),
+    ])
+}
+
+/// The current `PATH` without the JDK jaman switched to last, and with
+/// `java_home`'s `bin` in front, once
+fn path_with(java_home: Option<&Path>, current: &CurrentEnv) -> Result<String> {
+    let previous_bin = current
+        .jaman_java_home
+        .as_ref()
+        .map(|home| Path::new(home).join("bin"));
+    let bin = java_home.map(|home| home.join("bin"));
+
+    let mut path: Vec<PathBuf> = current
+        .path
+        .as_deref()
+        .map(|path| std::env::split_paths(OsStr::new(path)).collect())
+        .unwrap_or_default();
+    path.retain(|entry| Some(entry) != previous_bin.as_ref() && Some(entry) != bin.as_ref());
+    path.splice(0..0, bin);
+
+    Ok(std::env::join_paths(path)?.to_string_lossy().into_owned())
+}
+
+/// Statements that apply `changes` when evaluated by `shell`. Nushell cannot
+/// evaluate text, so it gets a record for `load-env` and a list to hide.
+/// JSON is one object mapping each name to its value, or `null` to remove it.
+pub fn render(shell: Shell, changes: &[EnvChange]) -> String {
+    if shell == Shell::Json {
+        let object: serde_json::Map<_, _> = changes
+            .iter()
+            .map(|change| (change.name.clone(), change.value.clone().into()))
+            .collect();
+        return serde_json::to_string_pretty(&object).unwrap_or_default();
+    }
+
+    if shell == Shell::Nu {
+        let mut set = serde_json::Map::new();
+        let mut unset = Vec::new();
+        for change in changes {
+            match &change.value {
+                Some(value) if change.name == "PATH" => {
+                    let entries: Vec<_> = std::env::split_paths(OsStr::new(value))
+                        .map(|entry| serde_json::Value::from(entry.to_string_lossy()))
+                        .collect();
+                    set.insert(change.name.clone(), entries.into());
+                }
+                Some(value) => {
+                    set.insert(change.name.clone(), value.clone().into());
+                }
+                None => unset.push(serde_json::Value::from(change.name.clone())),
+            }
+        }
+        return serde_json::json!({ "set": set, "unset": unset }).to_string();
+    }
+
+    changes
+        .iter()
+        .map(|change| match (shell, &change.value) {
+            (Shell::Bash | Shell::Zsh, Some(value)) => {
+                format!("export {}={};", change.name, posix_quote(value))
+            }
+            (Shell::Bash | Shell::Zsh, None) => format!("unset {};", change.name),
+            (Shell::Fish, Some(value)) if change.name == "PATH" => {
+                let entries: Vec<_> = std::env::split_paths(OsStr::new(value))
+                    .map(|entry| fish_quote(&entry.to_string_lossy()))
+                    .collect();
+                format!("set -gx PATH {};", entries.join(" "))
+            }
+            (Shell::Fish, Some(value)) => {
+                format!("set -gx {} {};", change.name, fish_quote(value))
+            }
+            (Shell::Fish, None) => format!("set -e {};", change.name),
+            (Shell::Pwsh, Some(value)) => {
+                format!("$env:{} = {};", change.name, pwsh_quote(value))
+            }
+            (Shell::Pwsh, None) => format!(
+                "Remove-Item -Path Env:{} -ErrorAction SilentlyContinue;",
+                change.name
+            ),
+            // `set "NAME="` removes the variable
+            (Shell::Cmd, value) => format!(
+                "set \"{}={}\"",
+                change.name,
+                value.as_deref().unwrap_or_default()
+            ),
+            (Shell::Nu | Shell::Json, _) => unreachable!("handled above"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Script for the shell's rc file that re-applies the version in effect
+/// before every prompt, which also covers directory changes
+pub fn hook(shell: Shell, jaman: &Path) -> Result<String> {
+    let jaman = jaman.to_string_lossy();
+    Ok(match shell {
+        Shell::Cmd | Shell::Json => anyhow::bail!(
+            "No prompt hook for '{}'; use 'jaman env --shell {}' to print the variables instead",
+            shell.name(),
+            shell.name()
+        ),
+        Shell::Bash => format!(
+            r#"export {var}=bash
+_jaman_hook() {{
+  local previous_exit_status=$?
+  eval "$({exe} hook bash --export)"
+  return $previous_exit_status
+}}
+if [[ ";${{PROMPT_COMMAND:-}};" != *";_jaman_hook;"* ]]; then
+  PROMPT_COMMAND="_jaman_hook${{PROMPT_COMMAND:+;$PROMPT_COMMAND}}"
+fi
+"#,
+            var = JAMAN_SHELL,
+            exe = posix_quote(&jaman)
+        ),
+        Shell::Zsh => format!(
+            r#"export {var}=zsh
+_jaman_hook() {{
+  eval "$({exe} hook zsh --export)"
+}}
+typeset -ag precmd_functions chpwd_functions
+if (( ! ${{precmd_functions[(I)_jaman_hook]}} )); then
+  precmd_functions=(_jaman_hook $precmd_functions)
+fi
+if (( ! ${{chpwd_functions[(I)_jaman_hook]}} )); then
+  chpwd_functions=(_jaman_hook $chpwd_functions)
+fi
+"#,
+            var = JAMAN_SHELL,
+            exe = posix_quote(&jaman)
+        ),
+        Shell::Fish => format!(
+            r#"set -gx {var} fish
+function __jaman_hook --on-event fish_prompt --on-variable PWD
+    {exe} hook fish --export | source
+end
+"#,
+            var = JAMAN_SHELL,
+            exe = fish_quote(&jaman)
+        ),
+        Shell::Pwsh => format!(
+            r#"$env:{var} = 'pwsh'
+if (-not (Test-Path Function:\__jaman_original_prompt)) {{
+    Copy-Item Function:\prompt Function:\global:__jaman_original_prompt
+}}
+function global:prompt {{
+    & {exe} hook pwsh --export | Out-String | Invoke-Expression
+    __jaman_original_prompt
+}}
+"#,
+            var = JAMAN_SHELL,
+            exe = pwsh_quote(&jaman)
+        ),
+        Shell::Nu => format!(
+            r#"$env.{var} = "nu"
+$env.config = ($env.config | upsert hooks.pre_prompt (
+    ($env.config.hooks.pre_prompt? | default []) | append {{||
+        let changes = (^{exe} hook nu --export | from json)
+        $changes.set | load-env
+        hide-env --ignore-errors ...$changes.unset
+    }}
+))
+"#,
+            var = JAMAN_SHELL,
+            exe = serde_json::Value::from(jaman.as_ref())
+        ),
+    })
+}
+
+/// Single-quote for bash and zsh: `'it'\''s'`
+fn posix_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn pwsh_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
@@ -0,0 +1,217 @@
+//! Java version numbers as defined by JEP 223, used to sort and compare
+//! installed and available versions
+
+use anyhow::Result;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed Java version: `$VNUM(-$PRE)?(\+$BUILD)?(-$OPT)?` as in JEP 223
+/// ("21.0.2+13", "26-ea+18", "17.0.10+7-LTS"), plus the legacy `1.8.0_292-b10`
+/// and `8u292` schemes. Trailing zeros of `$VNUM` are insignificant, so "21"
+/// and "21.0.0" are equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Version {
+    /// Feature, interim, update, patch and any further components, without
+    /// trailing zeros
+    numbers: Vec<u64>,
+    /// Pre-release identifier, e.g. "ea" or "beta"
+    pub pre: Option<String>,
+    pub build: Option<u64>,
+    /// Vendor-specific suffix, e.g. "LTS"
+    pub optional: Option<String>,
+    /// Character written before `optional`, "-" as in JEP 223 or "." as in
+    /// Temurin's "21.0.2+13.0.LTS", kept so the version displays as written
+    optional_separator: Option<char>,
+}
+
+impl Version {
+    fn number(&self, index: usize) -> u64 {
+        self.numbers.get(index).copied().unwrap_or(0)
+    }
+
+    /// Feature release, e.g. 21 for "21.0.2+13" and 8 for "1.8.0_292"
+    pub fn feature(&self) -> u64 {
+        self.number(0)
+    }
+
+    #[allow(dead_code)]
+    pub fn interim(&self) -> u64 {
+        self.number(1)
+    }
+
+    #[allow(dead_code)]
+    pub fn update(&self) -> u64 {
+        self.number(2)
+    }
+
+    #[allow(dead_code)]
+    pub fn patch(&self) -> u64 {
+        self.number(3)
+    }
+
+    /// Parse a version, also returning how many `$VNUM` components were
+    /// written, which trailing-zero trimming would otherwise lose: 3 for
+    /// "21.0.0", 1 for "21"
+    pub fn parse_with_precision(s: &str) -> Result<(Self, usize)> {
+        let invalid = || anyhow::anyhow!("Invalid Java version '{}'", s);
+
+        let mut rest = s.trim();
+        rest = rest.strip_prefix("jdk-").unwrap_or(rest);
+        // Legacy scheme: 1.8.0_292 is feature release 8, update 292
+        if let Some(legacy) = rest.strip_prefix("1.") {
+            if legacy.starts_with(|c: char| c.is_ascii_digit()) {
+                rest = legacy;
+            }
+        }
+
+        // $VNUM, with "_" and "u" accepted as separators of legacy updates
+        let vnum_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_' || c == 'u'))
+            .unwrap_or(rest.len());
+        let (vnum, mut rest) = rest.split_at(vnum_end);
+        // 8u292 is feature release 8, update 292
+        let vnum = match vnum.split_once('u') {
+            Some((feature, update)) => format!("{}.0.{}", feature, update),
+            None => vnum.to_string(),
+        };
+        let mut numbers = vnum
+            .split(['.', '_'])
+            .map(|part| part.parse::<u64>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>>>()?;
+        let precision = numbers.len();
+        while numbers.len() > 1 && numbers.last() == Some(&0) {
+            numbers.pop();
+        }
+        if numbers == [0] {
+            numbers.clear();
+        }
+
+        let mut pre = None;
+        let mut build = None;
+        if let Some(after) = rest.strip_prefix('-') {
+            let end = after.find('+').unwrap_or(after.len());
+            let (identifier, remainder) = after.split_at(end);
+            match identifier.strip_prefix('b').and_then(|b| b.parse().ok()) {
+                // Legacy build suffix, e.g. 1.8.0_292-b10
+                Some(legacy_build) if remainder.is_empty() => build = Some(legacy_build),
+                _ if identifier.is_empty() => return Err(invalid()),
+                _ => pre = Some(identifier.to_string()),
+            }
+            rest = remainder;
+        }
+
+        if let Some(after) = rest.strip_prefix('+') {
+            let end = after
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(after.len());
+            let (digits, remainder) = after.split_at(end);
+            if !digits.is_empty() {
+                build = Some(digits.parse().map_err(|_| invalid())?);
+            }
+            rest = remainder;
+        }
+
+        // Whatever follows is optional vendor information, e.g. "-LTS" or
+        // Temurin's ".0.202510171234" build timestamp
+        let optional_separator = rest.chars().next().filter(|c| matches!(c, '-' | '.'));
+        let optional = rest.trim_start_matches(['-', '.']);
+        let optional = (!optional.is_empty()).then(|| optional.to_string());
+
+        let version = Self {
+            numbers,
+            pre,
+            build,
+            optional_separator: optional_separator.filter(|_| optional.is_some()),
+            optional,
+        };
+        Ok((version, precision))
+    }
+
+    /// Compare only the first `len` `$VNUM` components, so "17.0.10" equals
+    /// "17" to one component
+    pub fn cmp_prefix(&self, other: &Self, len: usize) -> Ordering {
+        (0..len)
+            .map(|index| self.number(index).cmp(&other.number(index)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Compare as `Ord` does but without the vendor-specific `optional` part,
+    /// like JEP 223's `compareToIgnoreOptional`: Temurin's "21.0.2+13.0.LTS"
+    /// and Corretto's "21.0.2+13" are the same release
+    pub fn cmp_ignore_optional(&self, other: &Self) -> Ordering {
+        self.numbers
+            .cmp(&other.numbers)
+            // A pre-release comes before the release it leads up to
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+/// Compare two version strings, falling back to plain string comparison for
+/// strings that are not version numbers. Parsed versions sort above those.
+pub fn compare(a: &str, b: &str) -> Ordering {
+    match (a.parse::<Version>(), b.parse::<Version>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Greater,
+        (Err(_), Ok(_)) => Ordering::Less,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// `compare`, ignoring the vendor-specific optional part of both versions
+pub fn compare_ignore_optional(a: &str, b: &str) -> Ordering {
+    match (a.parse::<Version>(), b.parse::<Version>()) {
+        (Ok(a), Ok(b)) => a.cmp_ignore_optional(&b),
+        _ => compare(a, b),
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_ignore_optional(other)
+            .then_with(|| self.optional.cmp(&other.optional))
+            .then_with(|| self.optional_separator.cmp(&other.optional_separator))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Version {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_with_precision(s).map(|(version, _)| version)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let numbers: Vec<_> = self.numbers.iter().map(u64::to_string).collect();
+        if numbers.is_empty() {
+            write!(f, "0")?;
+        } else {
+            write!(f, "{}", numbers.join("."))?;
+        }
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        if let Some(build) = self.build {
+            write!(f, "+{}", build)?;
+        }
+        if let Some(optional) = &self.optional {
+            write!(f, "{}{}", self.optional_separator.unwrap_or('-'), optional)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+use jaman::cache::{ArchiveCache, CacheMode, MetadataCache};
+use jaman::config::{Channel, ImageType};
+use jaman::downloader::{AvailableVersion, Downloader};
+use jaman::lock::FileLock;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// A gzipped tarball holding a minimal JDK layout
+fn fake_jdk_archive() -> Vec<u8> {
+    let staging = tempfile::TempDir::new().unwrap();
+    let java = if cfg!(windows) { "java.exe" } else { "java" };
+    std::fs::create_dir_all(staging.path().join("bin")).unwrap();
+    std::fs::write(staging.path().join("bin").join(java), "").unwrap();
+    // Padding so the archive is large enough to split
+    std::fs::write(staging.path().join("lib"), "x".repeat(4096)).unwrap();
+
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::none());
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_dir_all("jdk-21", staging.path()).unwrap();
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+fn sha256_of(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[test]
+fn test_cache_key() {
+    let checksum = "ABC123".repeat(10) + "DEF0";
+    assert_eq!(
+        ArchiveCache::key("https://example.com/jdk.tar.gz", Some(&checksum)).unwrap(),
+        checksum.to_lowercase()
+    );
+
+    let by_url = ArchiveCache::key("https://example.com/jdk.tar.gz", None).unwrap();
+    assert!(by_url.starts_with("url-"));
+    assert_ne!(
+        by_url,
+        ArchiveCache::key("https://example.com/other.tar.gz", None).unwrap()
+    );
+
+    // Checksums name cache directories, so nothing but SHA-256 digests is accepted
+    for checksum in ["abc123", "../../x", &format!("../{}", "a".repeat(61))] {
+        assert!(ArchiveCache::key("https://example.com/jdk.tar.gz", Some(checksum)).is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_download_refuses_invalid_checksum_before_touching_disk() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("downloads");
+    let outside = temp_dir.path().join("outside");
+    std::fs::create_dir_all(&outside).unwrap();
+
+    let error = Downloader::new()
+        .with_cache_dir(&cache_dir)
+        .install_from_url(
+            "http://127.0.0.1:9/jdk-21.tar.gz",
+            Some("../outside"),
+            &temp_dir.path().join("jdks"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("Invalid SHA-256 checksum"));
+    assert!(outside.exists());
+    assert!(!cache_dir.exists());
+}
+
+#[test]
+fn test_cache_entries_and_clean() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache = ArchiveCache::new(temp_dir.path());
+
+    std::fs::create_dir_all(cache.entry_dir("done")).unwrap();
+    std::fs::write(cache.partial_path("done"), vec![0u8; 64]).unwrap();
+    let archive = cache.complete("done", "jdk-21.tar.gz").unwrap();
+    assert_eq!(cache.lookup("done"), Some(archive));
+
+    std::fs::create_dir_all(cache.entry_dir("unfinished")).unwrap();
+    std::fs::write(cache.partial_path("unfinished"), vec![0u8; 16]).unwrap();
+    assert_eq!(cache.lookup("unfinished"), None);
+
+    let entries = cache.entries().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].size, 64);
+    assert!(!entries[0].partial);
+    assert!(entries[1].partial);
+
+    let removed = cache.clean(true).unwrap();
+    assert_eq!(removed.len(), 1);
+    assert!(!cache.entry_dir("unfinished").exists());
+    assert!(cache.lookup("done").is_some());
+
+    cache.clean(false).unwrap();
+    assert!(cache.entries().unwrap().is_empty());
+}
+
+#[test]
+fn test_clean_keeps_downloads_in_progress() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache = ArchiveCache::new(temp_dir.path());
+    for key in ["busy", "idle"] {
+        std::fs::create_dir_all(cache.entry_dir(key)).unwrap();
+        std::fs::write(cache.partial_path(key), vec![0u8; 16]).unwrap();
+    }
+
+    // Another process is still downloading "busy"
+    let lock = FileLock::exclusive(&cache.lock_path("busy"), "a download").unwrap();
+    let removed = cache.clean(false).unwrap();
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].key, "idle");
+    assert!(cache.partial_path("busy").exists());
+
+    drop(lock);
+    cache.clean(false).unwrap();
+    assert!(cache.entries().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_download_reuses_cached_archive() {
+    let archive = fake_jdk_archive();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jdk-21.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(archive.clone()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("downloads");
+    let downloader = Downloader::new().with_cache_dir(&cache_dir);
+    let url = format!("{}/jdk-21.tar.gz", server.uri());
+    let checksum = sha256_of(&archive);
+
+    for install_dir in ["first", "second"] {
+        let jdk_root = downloader
+            .install_from_url(&url, Some(&checksum), &temp_dir.path().join(install_dir))
+            .await
+            .unwrap();
+        assert!(jdk_root.ends_with("jdk-21/jdk-21"));
+    }
+
+    let cache = ArchiveCache::new(&cache_dir);
+    assert!(cache.lookup(&checksum).unwrap().ends_with("jdk-21.tar.gz"));
+}
+
+#[tokio::test]
+async fn test_download_resumes_partial_archive() {
+    let archive = fake_jdk_archive();
+    let (head, tail) = archive.split_at(archive.len() / 2);
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jdk-21.tar.gz"))
+        .and(header("range", format!("bytes={}-", head.len()).as_str()))
+        .respond_with(ResponseTemplate::new(206).set_body_bytes(tail.to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache = ArchiveCache::new(temp_dir.path().join("downloads"));
+    let checksum = sha256_of(&archive);
+    std::fs::create_dir_all(cache.entry_dir(&checksum)).unwrap();
+    std::fs::write(cache.partial_path(&checksum), head).unwrap();
+
+    let downloader = Downloader::new().with_cache_dir(cache.dir());
+    downloader
+        .install_from_url(
+            &format!("{}/jdk-21.tar.gz", server.uri()),
+            Some(&checksum),
+            &temp_dir.path().join("jdks"),
+        )
+        .await
+        .unwrap();
+
+    let cached = cache.lookup(&checksum).unwrap();
+    assert_eq!(std::fs::read(cached).unwrap(), archive);
+    assert!(!cache.partial_path(&checksum).exists());
+}
+
+#[tokio::test]
+async fn test_download_evicts_corrupted_archive() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jdk-21.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"not the archive".to_vec()))
+        .mount(&server)
+        .await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache = ArchiveCache::new(temp_dir.path().join("downloads"));
+    let checksum = sha256_of(&fake_jdk_archive());
+
+    let result = Downloader::new()
+        .with_cache_dir(cache.dir())
+        .install_from_url(
+            &format!("{}/jdk-21.tar.gz", server.uri()),
+            Some(&checksum),
+            &temp_dir.path().join("jdks"),
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert!(cache.lookup(&checksum).is_none());
+    assert!(!cache.entry_dir(&checksum).exists());
+}
+
+#[tokio::test]
+async fn test_cached_archive_found_by_url() {
+    let archive = fake_jdk_archive();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jdk-21.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(archive.clone()))
+        .mount(&server)
+        .await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache = ArchiveCache::new(temp_dir.path().join("downloads"));
+    let url = format!("{}/jdk-21.tar.gz", server.uri());
+    Downloader::new()
+        .with_cache_dir(cache.dir())
+        .install_from_url(
+            &url,
+            Some(&sha256_of(&archive)),
+            &temp_dir.path().join("jdks"),
+        )
+        .await
+        .unwrap();
+
+    assert!(cache.lookup_url(&url).unwrap().ends_with("jdk-21.tar.gz"));
+    assert!(
+        cache
+            .lookup_url("https://example.com/other.tar.gz")
+            .is_none()
+    );
+
+    // The source marker is not an archive of its own
+    let entries = cache.entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    cache.clean(false).unwrap();
+    assert!(!cache.entry_dir(&sha256_of(&archive)).exists());
+}
+
+#[tokio::test]
+async fn test_offline_install_uses_cached_archive() {
+    let archive = fake_jdk_archive();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jdk-21.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(archive))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("downloads");
+    let url = format!("{}/jdk-21.tar.gz", server.uri());
+    Downloader::new()
+        .with_cache_dir(&cache_dir)
+        .install_from_url(&url, None, &temp_dir.path().join("first"))
+        .await
+        .unwrap();
+
+    let offline = Downloader::new()
+        .with_cache_dir(&cache_dir)
+        .with_cache_mode(CacheMode::Offline);
+    let jdk_root = offline
+        .install_from_url(&url, None, &temp_dir.path().join("second"))
+        .await
+        .unwrap();
+    assert!(jdk_root.ends_with("jdk-21/jdk-21"));
+
+    let missing = offline
+        .install_from_url(
+            &format!("{}/jdk-17.tar.gz", server.uri()),
+            None,
+            &temp_dir.path().join("third"),
+        )
+        .await;
+    assert!(missing.is_err());
+}
+
+fn available_version(version: &str) -> AvailableVersion {
+    AvailableVersion {
+        version: version.to_string(),
+        vendor: "Eclipse Temurin".to_string(),
+        is_lts: true,
+        architecture: "x64".to_string(),
+        download_url: "https://example.com/jdk.tar.gz".to_string(),
+        checksum: None,
+        javafx_bundled: false,
+        image_type: ImageType::Jdk,
+        channel: Channel::Ga,
+    }
+}
+
+#[test]
+fn test_metadata_cache_ttl() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let fresh = MetadataCache::new(temp_dir.path(), chrono::Duration::hours(24));
+    let expired = MetadataCache::new(temp_dir.path(), chrono::Duration::zero());
+
+    assert!(fresh.load("available").is_none());
+    fresh
+        .store("available", &[available_version("21.0.2+13")])
+        .unwrap();
+
+    let cached = fresh.load_fresh("available").unwrap();
+    assert_eq!(cached.versions.len(), 1);
+    assert_eq!(cached.versions[0].version, "21.0.2+13");
+
+    // Expired listings are still available to offline mode
+    assert!(expired.load_fresh("available").is_none());
+    assert!(expired.load("available").is_some());
+}
@@ -1,4 +1,8 @@
-use jaman::config::{Config, JavaVersion};
+use jaman::config::{
+    CONFIG_BACKUPS, Channel, Config, ImageType, JavaVersion, MetadataBackend, NetworkSettings,
+    ProxySettings, ReleaseSelection,
+};
+use jaman::migrations::SCHEMA_VERSION;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -10,7 +14,7 @@ fn test_config_new() {
 
     assert_eq!(config.installation_dir, install_dir);
     assert_eq!(config.download_dir, download_dir);
-    assert_eq!(config.active_version, None);
+    assert_eq!(config.active_id, None);
     assert_eq!(config.installed_versions.len(), 0);
 }
 
@@ -67,21 +71,22 @@ fn test_add_version_duplicate() {
 fn test_remove_version() {
     let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
 
-    let path = PathBuf::from("/test/java21");
     let version = JavaVersion::new(
         "21.0.1".to_string(),
         "Eclipse Temurin".to_string(),
-        path.clone(),
+        PathBuf::from("/test/java21"),
         true,
         "x64".to_string(),
         false,
     );
 
-    config.add_version(version);
+    let id = config.add_version(version);
     assert_eq!(config.installed_versions.len(), 1);
+    config.set_active(&id).unwrap();
 
-    config.remove_version(&path);
+    config.remove_version(&id);
     assert_eq!(config.installed_versions.len(), 0);
+    assert_eq!(config.active_id, None);
 }
 
 #[test]
@@ -100,11 +105,10 @@ fn test_get_version() {
     config.add_version(version);
 
     let found = config.get_version("21");
-    assert!(found.is_some());
     assert_eq!(found.unwrap().version, "21.0.1");
 
     let not_found = config.get_version("17");
-    assert!(not_found.is_none());
+    assert!(not_found.is_err());
 }
 
 #[test]
@@ -120,11 +124,16 @@ fn test_set_active() {
         false,
     );
 
-    config.add_version(version);
+    let id = config.add_version(version);
+    assert_eq!(id, "temurin-21.0.1-x64");
 
-    let result = config.set_active("21.0.1");
+    let result = config.set_active(&id);
     assert!(result.is_ok());
-    assert_eq!(config.active_version, Some("21.0.1".to_string()));
+    assert_eq!(config.active_id, Some(id));
+    assert_eq!(config.active().unwrap().version, "21.0.1");
+
+    // A version string is not an installation ID
+    assert!(config.set_active("21.0.1").is_err());
 }
 
 #[test]
@@ -217,3 +226,527 @@ fn test_config_save_and_load() {
     // This is a simplified test
     assert_eq!(config.installed_versions.len(), 1);
 }
+
+#[test]
+fn test_metadata_backend_defaults_to_direct() {
+    let config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    assert_eq!(config.metadata_backend, MetadataBackend::Direct);
+
+    // Configs written before the backend setting existed still load
+    let content = r#"
+installation_dir = "/test"
+download_dir = "/test/downloads"
+installed_versions = []
+"#;
+    let config: Config = toml::from_str(content).unwrap();
+    assert_eq!(config.metadata_backend, MetadataBackend::Direct);
+}
+
+#[test]
+fn test_metadata_backend_round_trip() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    config.metadata_backend = "foojay".parse().unwrap();
+
+    let content = toml::to_string_pretty(&config).unwrap();
+    assert!(content.contains("metadata_backend = \"foojay\""));
+
+    let loaded: Config = toml::from_str(&content).unwrap();
+    assert_eq!(loaded.metadata_backend, MetadataBackend::Foojay);
+
+    assert!("aggregator".parse::<MetadataBackend>().is_err());
+}
+
+#[test]
+fn test_release_selection_parse() {
+    assert_eq!(
+        "lts".parse::<ReleaseSelection>().unwrap(),
+        ReleaseSelection::AllLts
+    );
+    assert_eq!(
+        "GA".parse::<ReleaseSelection>().unwrap(),
+        ReleaseSelection::AllGa
+    );
+    assert_eq!(
+        "17..21".parse::<ReleaseSelection>().unwrap(),
+        ReleaseSelection::Range {
+            min: Some(17),
+            max: Some(21)
+        }
+    );
+    assert_eq!(
+        "11..".parse::<ReleaseSelection>().unwrap(),
+        ReleaseSelection::Range {
+            min: Some(11),
+            max: None
+        }
+    );
+    assert_eq!(
+        "21".parse::<ReleaseSelection>().unwrap(),
+        ReleaseSelection::Range {
+            min: Some(21),
+            max: Some(21)
+        }
+    );
+    assert!("newest".parse::<ReleaseSelection>().is_err());
+    assert!("17..x".parse::<ReleaseSelection>().is_err());
+}
+
+#[test]
+fn test_release_selection_includes() {
+    assert!(ReleaseSelection::AllLts.includes(8, true));
+    assert!(!ReleaseSelection::AllLts.includes(22, false));
+    assert!(ReleaseSelection::AllGa.includes(22, false));
+
+    let range: ReleaseSelection = "17..21".parse().unwrap();
+    assert!(!range.includes(11, true));
+    assert!(range.includes(17, true));
+    assert!(range.includes(19, false));
+    assert!(range.includes(21, true));
+    assert!(!range.includes(22, false));
+
+    let open_ended: ReleaseSelection = "..11".parse().unwrap();
+    assert!(open_ended.includes(8, true));
+    assert!(!open_ended.includes(17, true));
+}
+
+#[test]
+fn test_release_selection_round_trip() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    assert_eq!(config.release_selection, ReleaseSelection::AllGa);
+
+    config.release_selection = "17..".parse().unwrap();
+    let content = toml::to_string_pretty(&config).unwrap();
+    assert!(content.contains("release_selection = \"17..\""));
+
+    let loaded: Config = toml::from_str(&content).unwrap();
+    assert_eq!(loaded.release_selection, config.release_selection);
+}
+
+#[test]
+fn test_image_type_defaults_to_jdk() {
+    let version = JavaVersion::new(
+        "21.0.1".to_string(),
+        "Eclipse Temurin".to_string(),
+        PathBuf::from("/test/java21"),
+        true,
+        "x64".to_string(),
+        false,
+    );
+    assert_eq!(version.image_type, ImageType::Jdk);
+    assert_eq!(ImageType::Jdk.badge(), None);
+    assert_eq!(ImageType::Jre.badge(), Some("JRE"));
+
+    assert_eq!("JRE".parse::<ImageType>().unwrap(), ImageType::Jre);
+    assert_eq!(
+        "headless".parse::<ImageType>().unwrap(),
+        ImageType::Headless
+    );
+    assert!("jmods".parse::<ImageType>().is_err());
+}
+
+#[test]
+fn test_image_type_serialization() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    let mut version = JavaVersion::new(
+        "21.0.1".to_string(),
+        "Eclipse Temurin".to_string(),
+        PathBuf::from("/test/jre21"),
+        true,
+        "x64".to_string(),
+        false,
+    );
+    version.image_type = ImageType::Jre;
+    config.add_version(version);
+
+    let content = toml::to_string_pretty(&config).unwrap();
+    assert!(content.contains("image_type = \"jre\""));
+
+    let loaded: Config = toml::from_str(&content).unwrap();
+    assert_eq!(loaded.installed_versions[0].image_type, ImageType::Jre);
+}
+
+fn installed(version: &str, vendor: &str, channel: Channel) -> JavaVersion {
+    let mut java_version = JavaVersion::new(
+        version.to_string(),
+        vendor.to_string(),
+        PathBuf::from(format!("/test/{}-{}", vendor, version)),
+        false,
+        "x64".to_string(),
+        false,
+    );
+    java_version.channel = channel;
+    java_version
+}
+
+#[test]
+fn test_channel_defaults_to_ga() {
+    let version = installed("21.0.1", "Eclipse Temurin", Channel::default());
+    assert_eq!(version.channel, Channel::Ga);
+
+    assert_eq!("EA".parse::<Channel>().unwrap(), Channel::Ea);
+    assert!("nightly".parse::<Channel>().is_err());
+
+    // Versions recorded before the channel existed load as GA
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    config.add_version(installed("26-ea+20", "Eclipse Temurin", Channel::Ea));
+    let content = toml::to_string_pretty(&config).unwrap();
+    assert!(content.contains("channel = \"ea\""));
+
+    let legacy: Config = toml::from_str(&content.replace("channel = \"ea\"", "")).unwrap();
+    assert_eq!(legacy.installed_versions[0].channel, Channel::Ga);
+}
+
+#[test]
+fn test_superseded_early_access() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    config.add_version(installed("25-ea+30", "Eclipse Temurin", Channel::Ea));
+    config.add_version(installed("25.0.1+8", "Eclipse Temurin", Channel::Ga));
+    let ea_18 = config.add_version(installed("26-ea+18", "Eclipse Temurin", Channel::Ea));
+    config.add_version(installed("26-ea+20", "Eclipse Temurin", Channel::Ea));
+    config.add_version(installed("26-ea+5", "Azul Zulu", Channel::Ea));
+    config.add_version(installed("27-ea+2", "Eclipse Temurin", Channel::Ea));
+
+    let superseded: Vec<_> = config
+        .superseded_early_access()
+        .into_iter()
+        .map(|v| v.version.as_str())
+        .collect();
+    assert_eq!(superseded, vec!["25-ea+30", "26-ea+18"]);
+
+    // The active version is never expired
+    config.active_id = Some(ea_18);
+    assert_eq!(config.superseded_early_access().len(), 1);
+}
+
+#[test]
+fn test_network_settings() {
+    let config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    assert_eq!(config.network, NetworkSettings::default());
+    assert_eq!(config.network.retries, 3);
+
+    // Configs without a [network] table, or with only some keys, use the defaults
+    let content = toml::to_string_pretty(&config).unwrap();
+    let without_network = content[..content.find("[network]").unwrap()].to_string();
+    let loaded: Config = toml::from_str(&without_network).unwrap();
+    assert_eq!(loaded.network, NetworkSettings::default());
+
+    let partial: Config =
+        toml::from_str(&format!("{}[network]\nretries = 0\n", without_network)).unwrap();
+    assert_eq!(partial.network.retries, 0);
+    assert_eq!(partial.network.read_timeout_secs, 30);
+}
+
+#[test]
+fn test_proxy_and_ca_settings_round_trip() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    assert_eq!(config.proxy, ProxySettings::default());
+    assert!(config.ca_certificates.is_empty());
+
+    config.proxy = ProxySettings {
+        url: Some("http://proxy.corp.example:3128".to_string()),
+        username: Some("build".to_string()),
+        password: Some("secret".to_string()),
+        no_proxy: vec!["localhost".to_string(), ".corp.example".to_string()],
+    };
+    config.ca_certificates = vec![PathBuf::from("/etc/ssl/corp-root.pem")];
+
+    let content = toml::to_string_pretty(&config).unwrap();
+    let loaded: Config = toml::from_str(&content).unwrap();
+
+    assert_eq!(loaded.proxy, config.proxy);
+    assert_eq!(loaded.ca_certificates, config.ca_certificates);
+}
+
+#[test]
+fn test_metadata_ttl_defaults_to_a_day() {
+    let config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    assert_eq!(config.metadata_ttl_hours, 24);
+
+    let content = toml::to_string_pretty(&config).unwrap();
+    let legacy: Config = toml::from_str(&content.replace("metadata_ttl_hours = 24\n", "")).unwrap();
+    assert_eq!(legacy.metadata_ttl_hours, 24);
+}
+
+#[test]
+fn test_save_keeps_rotating_backups() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_file = temp_dir.path().join("config.toml");
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+
+    for version in ["8", "11", "17", "21", "25"] {
+        config.active_id = Some(version.to_string());
+        config.save_to(&config_file).unwrap();
+    }
+    // Saving an unchanged config does not rotate the backups
+    config.save_to(&config_file).unwrap();
+
+    let active = |path: &std::path::Path| Config::load_from(path).unwrap().active_id;
+    assert_eq!(active(&config_file), Some("25".to_string()));
+    assert_eq!(
+        active(&Config::backup_path(&config_file, 1)),
+        Some("21".to_string())
+    );
+    assert_eq!(
+        active(&Config::backup_path(&config_file, CONFIG_BACKUPS)),
+        Some("11".to_string())
+    );
+    assert!(!Config::backup_path(&config_file, CONFIG_BACKUPS + 1).exists());
+
+    // No temporary files are left behind
+    assert_eq!(
+        std::fs::read_dir(temp_dir.path()).unwrap().count(),
+        1 + CONFIG_BACKUPS
+    );
+}
+
+#[test]
+fn test_restore_backup_after_corruption() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_file = temp_dir.path().join("config.toml");
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    config.save_to(&config_file).unwrap();
+    for version in ["17", "21"] {
+        config.active_id = Some(version.to_string());
+        config.save_to(&config_file).unwrap();
+    }
+
+    // A write cut short by a crash, and a damaged newest backup
+    std::fs::write(&config_file, "installation_dir = \"/te").unwrap();
+    std::fs::write(Config::backup_path(&config_file, 1), "not = [valid").unwrap();
+    assert!(Config::load_from(&config_file).is_err());
+
+    let (backup, restored) = Config::restore_backup(&config_file).unwrap().unwrap();
+    assert_eq!(backup, Config::backup_path(&config_file, 2));
+    assert_eq!(restored.active_id, None);
+    assert!(Config::load_from(&config_file).is_ok());
+    assert!(temp_dir.path().join("config.toml.damaged").exists());
+}
+
+#[test]
+fn test_restore_backup_without_backups() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_file = temp_dir.path().join("config.toml");
+    std::fs::write(&config_file, "garbage").unwrap();
+
+    assert!(Config::restore_backup(&config_file).unwrap().is_none());
+    assert!(config_file.exists());
+}
+
+#[test]
+fn test_load_v0_config() {
+    let config = Config::from_toml(include_str!("fixtures/config/v0.toml")).unwrap();
+
+    assert_eq!(config.schema_version, SCHEMA_VERSION);
+    assert_eq!(
+        config.download_dir,
+        PathBuf::from("/home/dev/.local/share/jaman/downloads")
+    );
+    assert_eq!(config.active_id.as_deref(), Some("temurin-21.0.1-x64"));
+    assert_eq!(config.installed_versions.len(), 2);
+    assert_eq!(config.installed_versions[0].image_type, ImageType::Jdk);
+    assert_eq!(config.installed_versions[0].channel, Channel::Ga);
+    assert!(config.installed_versions[1].auto_detected);
+    assert_eq!(config.network, NetworkSettings::default());
+    assert_eq!(config.metadata_ttl_hours, 24);
+}
+
+#[test]
+fn test_load_v1_config() {
+    let config = Config::from_toml(include_str!("fixtures/config/v1.toml")).unwrap();
+
+    assert_eq!(config.schema_version, SCHEMA_VERSION);
+    assert_eq!(config.download_dir, PathBuf::from("/home/dev/.cache/jaman"));
+    assert_eq!(config.metadata_backend, MetadataBackend::Foojay);
+    assert_eq!(config.release_selection, ReleaseSelection::AllLts);
+    assert_eq!(config.network.retries, 5);
+    assert_eq!(config.metadata_ttl_hours, 6);
+    assert_eq!(config.installed_versions[0].channel, Channel::Ea);
+    assert_eq!(config.installed_versions[1].image_type, ImageType::Jre);
+    assert!(config.installed_versions[1].javafx_bundled);
+
+    assert_eq!(config.installed_versions[0].id, "temurin-26-ea+18-x64");
+    assert_eq!(config.installed_versions[1].id, "zulu-21.0.2+13-x64-jre-fx");
+    assert_eq!(config.active_id.as_deref(), Some("temurin-26-ea+18-x64"));
+}
+
+#[test]
+fn test_load_v2_config() {
+    let content = include_str!("fixtures/config/v2.toml");
+    let config = Config::from_toml(content).unwrap();
+
+    assert_eq!(config.schema_version, 2);
+    assert_eq!(config.active().unwrap().vendor, "Eclipse Temurin");
+    assert_eq!(config.installed_versions[1].id, "zulu-21.0.2+13-x64-jre-fx");
+    // Migrating the previous layout gives the same result
+    let migrated = Config::from_toml(include_str!("fixtures/config/v1.toml")).unwrap();
+    assert_eq!(
+        toml::to_string(&migrated).unwrap(),
+        toml::to_string(&config).unwrap()
+    );
+}
+
+#[test]
+fn test_migration_gives_same_version_installs_distinct_ids() {
+    let entry = |vendor: &str, path: &str| {
+        format!(
+            "[[installed_versions]]\nversion = \"21.0.2\"\nvendor = \"{}\"\npath = \"{}\"\n\
+             is_lts = true\narchitecture = \"x64\"\nauto_detected = false\n",
+            vendor, path
+        )
+    };
+    let content = format!(
+        "schema_version = 1\ninstallation_dir = \"/jdks\"\ndownload_dir = \"/downloads\"\n\
+         active_version = \"21.0.2\"\n{}{}{}",
+        entry("Amazon Corretto", "/jdks/corretto"),
+        entry("Eclipse Temurin", "/jdks/temurin"),
+        entry("Eclipse Temurin", "/usr/lib/jvm/temurin-21"),
+    );
+
+    let config = Config::from_toml(&content).unwrap();
+    let ids: Vec<_> = config
+        .installed_versions
+        .iter()
+        .map(|v| v.id.as_str())
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            "corretto-21.0.2-x64",
+            "temurin-21.0.2-x64",
+            "temurin-21.0.2-x64-2"
+        ]
+    );
+    // 0.1.0 activated the first install of the version
+    assert_eq!(config.active_id.as_deref(), Some("corretto-21.0.2-x64"));
+}
+
+#[test]
+fn test_add_version_keeps_ids_stable_and_unique() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    let temurin = |path: &str| {
+        JavaVersion::new(
+            "21.0.2+13".to_string(),
+            "Eclipse Temurin".to_string(),
+            PathBuf::from(path),
+            true,
+            "x64".to_string(),
+            false,
+        )
+    };
+
+    assert_eq!(config.add_version(temurin("/a")), "temurin-21.0.2+13-x64");
+    assert_eq!(config.add_version(temurin("/b")), "temurin-21.0.2+13-x64-2");
+    // Re-adding a path, as a rescan does, keeps its ID
+    assert_eq!(config.add_version(temurin("/b")), "temurin-21.0.2+13-x64-2");
+
+    let mut jre = temurin("/c");
+    jre.image_type = ImageType::Jre;
+    assert_eq!(config.add_version(jre), "temurin-21.0.2+13-x64-jre");
+
+    let mut unknown = temurin("/d");
+    unknown.vendor = "Oracle Corporation".to_string();
+    assert_eq!(
+        config.add_version(unknown),
+        "oracle-corporation-21.0.2+13-x64"
+    );
+
+    assert_eq!(
+        config.get_version("temurin-21.0.2+13-x64-2").unwrap().path,
+        PathBuf::from("/b")
+    );
+}
+
+#[test]
+fn test_upgraded_config_saves_current_schema() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_file = temp_dir.path().join("config.toml");
+    std::fs::write(&config_file, include_str!("fixtures/config/v0.toml")).unwrap();
+
+    let mut config = Config::load_from(&config_file).unwrap();
+    config.active_id = None;
+    config.save_to(&config_file).unwrap();
+
+    let saved = std::fs::read_to_string(&config_file).unwrap();
+    assert!(saved.starts_with(&format!("schema_version = {}", SCHEMA_VERSION)));
+    assert!(saved.contains("download_dir"));
+    // The original layout is kept as a backup
+    assert!(
+        std::fs::read_to_string(Config::backup_path(&config_file, 1))
+            .unwrap()
+            .contains("jaman 0.1.0")
+    );
+}
+
+#[test]
+fn test_rejects_newer_schema() {
+    let content = include_str!("fixtures/config/v1.toml").replace(
+        "schema_version = 1",
+        &format!("schema_version = {}", SCHEMA_VERSION + 1),
+    );
+
+    let error = Config::from_toml(&content).unwrap_err().to_string();
+    assert!(error.contains("upgrade jaman"));
+}
+
+#[test]
+fn test_get_version_prefers_newest_match() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    for version in ["17.0.10", "17.0.9", "21.0.2"] {
+        config.add_version(JavaVersion::new(
+            version.to_string(),
+            "Eclipse Temurin".to_string(),
+            PathBuf::from("/test").join(version),
+            true,
+            "x64".to_string(),
+            false,
+        ));
+    }
+
+    assert_eq!(config.get_version("17").unwrap().version, "17.0.10");
+}
+
+#[test]
+fn test_get_version_does_not_match_substrings() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    for version in ["11.0.22", "17.0.7", "21.0.7"] {
+        config.add_version(JavaVersion::new(
+            version.to_string(),
+            "Eclipse Temurin".to_string(),
+            PathBuf::from("/test").join(version),
+            true,
+            "x64".to_string(),
+            false,
+        ));
+    }
+
+    assert!(config.get_version("1").is_err());
+    assert!(config.get_version("7").is_err());
+    assert_eq!(config.get_version("17").unwrap().version, "17.0.7");
+}
+
+#[test]
+fn test_get_version_reports_ambiguous_matches() {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    for vendor in ["Eclipse Temurin", "Amazon Corretto"] {
+        config.add_version(JavaVersion::new(
+            "21.0.2".to_string(),
+            vendor.to_string(),
+            PathBuf::from("/test").join(vendor),
+            true,
+            "x64".to_string(),
+            false,
+        ));
+    }
+
+    let error = config.get_version("21").unwrap_err().to_string();
+    assert!(error.contains("ambiguous"));
+    assert!(error.contains("Eclipse Temurin"));
+    assert!(error.contains("Amazon Corretto"));
+    assert!(error.contains("[corretto-21.0.2-x64]"));
+    assert!(error.contains("jaman list"));
+
+    assert_eq!(
+        config.get_version("corretto@21").unwrap().vendor,
+        "Amazon Corretto"
+    );
+}
@@ -1,3 +1,4 @@
+use jaman::config::ImageType;
 use jaman::detector::JavaDetector;
 use std::path::PathBuf;
 
@@ -94,3 +95,73 @@ fn test_parse_vendor_from_output() {
         );
     }
 }
+
+#[test]
+fn test_has_native_image() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let bin_dir = temp_dir.path().join("bin");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+
+    assert!(!JavaDetector::has_native_image(temp_dir.path()));
+
+    let tool = if cfg!(windows) {
+        "native-image.cmd"
+    } else {
+        "native-image"
+    };
+    std::fs::write(bin_dir.join(tool), "").unwrap();
+
+    assert!(JavaDetector::has_native_image(temp_dir.path()));
+}
+
+#[test]
+fn test_detect_image_type() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let bin_dir = temp_dir.path().join("bin");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+
+    assert_eq!(
+        JavaDetector::detect_image_type(temp_dir.path()),
+        ImageType::Jre
+    );
+
+    let javac = if cfg!(windows) { "javac.exe" } else { "javac" };
+    std::fs::write(bin_dir.join(javac), "").unwrap();
+
+    assert_eq!(
+        JavaDetector::detect_image_type(temp_dir.path()),
+        ImageType::Jdk
+    );
+}
+
+#[test]
+fn test_inspect_reads_release_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let bin_dir = temp_dir.path().join("bin");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+    let javac = if cfg!(windows) { "javac.exe" } else { "javac" };
+    std::fs::write(bin_dir.join(javac), "").unwrap();
+    std::fs::write(
+        temp_dir.path().join("release"),
+        "IMPLEMENTOR=\"Amazon.com Inc.\"\nJAVA_VERSION=\"17.0.10\"\nOS_ARCH=\"aarch64\"\n",
+    )
+    .unwrap();
+
+    let version = JavaDetector::inspect(temp_dir.path()).unwrap();
+
+    assert_eq!(version.version, "17.0.10");
+    assert_eq!(version.vendor, "Amazon Corretto");
+    assert!(version.is_lts);
+    assert!(!version.auto_detected);
+    assert_eq!(version.image_type, ImageType::Jdk);
+
+    // Java 8 reports the legacy "1.8.0_402" form
+    std::fs::write(
+        temp_dir.path().join("release"),
+        "JAVA_VERSION=\"1.8.0_402\"\n",
+    )
+    .unwrap();
+    let version = JavaDetector::inspect(temp_dir.path()).unwrap();
+    assert_eq!(version.version, "8.0_402");
+    assert_eq!(version.vendor, "Unknown");
+}
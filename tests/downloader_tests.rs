@@ -1,4 +1,5 @@
-use jaman::downloader::{AvailableVersion, Downloader};
+use jaman::config::{Channel, ImageType, JavaVersion};
+use jaman::downloader::{AvailableVersion, Downloader, LOCK_DIR, STAGING_DIR};
 
 #[tokio::test]
 async fn test_downloader_new() {
@@ -15,7 +16,8 @@ async fn test_fetch_available_versions() {
     // Should return successfully
     assert!(result.is_ok());
 
-    if let Ok(versions) = result {
+    if let Ok(report) = result {
+        let versions = report.versions;
         // Should have some versions
         assert!(!versions.is_empty());
 
@@ -37,6 +39,9 @@ fn test_available_version_creation() {
         architecture: "x64".to_string(),
         download_url: "https://example.com/java.zip".to_string(),
         checksum: Some("abc123".to_string()),
+        javafx_bundled: false,
+        image_type: ImageType::Jdk,
+        channel: Channel::Ga,
     };
 
     assert_eq!(version.version, "21.0.1");
@@ -114,6 +119,9 @@ async fn test_download_and_install() {
         architecture: "x64".to_string(),
         download_url: "https://example.com/java.zip".to_string(),
         checksum: None,
+        javafx_bundled: false,
+        image_type: ImageType::Jdk,
+        channel: Channel::Ga,
     };
 
     // This would require a real download
@@ -133,3 +141,348 @@ fn test_checksum_verification() {
     assert!(!hash_str.is_empty());
     assert_eq!(hash_str.len(), 64); // SHA256 produces 64 hex characters
 }
+
+/// A gzipped tarball holding a minimal JDK layout under `jdk-21.0.2+13/`
+fn fake_jdk_archive(dir: &std::path::Path) -> std::path::PathBuf {
+    let jdk = dir.join("src").join("jdk-21.0.2+13");
+    std::fs::create_dir_all(jdk.join("bin")).unwrap();
+    let java = if cfg!(windows) { "java.exe" } else { "java" };
+    std::fs::write(jdk.join("bin").join(java), "").unwrap();
+    std::fs::write(
+        jdk.join("release"),
+        "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"21.0.2\"\n",
+    )
+    .unwrap();
+
+    let archive = dir.join("OpenJDK21U-jdk_x64_linux.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&archive).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_dir_all("jdk-21.0.2+13", &jdk).unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    archive
+}
+
+fn sha256_of(path: &std::path::Path) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(std::fs::read(path).unwrap()))
+}
+
+#[test]
+fn test_install_archive() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let install_dir = temp_dir.path().join("jdks");
+    let archive = fake_jdk_archive(temp_dir.path());
+    let downloader = Downloader::new();
+
+    let jdk_root = downloader
+        .install_archive(&archive, Some(&sha256_of(&archive)), &install_dir)
+        .unwrap();
+
+    assert_eq!(
+        jdk_root,
+        install_dir
+            .join("OpenJDK21U-jdk_x64_linux")
+            .join("jdk-21.0.2+13")
+    );
+    assert!(jdk_root.join("release").exists());
+
+    // Installing the same archive twice is refused
+    assert!(
+        downloader
+            .install_archive(&archive, None, &install_dir)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_install_archive_rejects_checksum_mismatch() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let install_dir = temp_dir.path().join("jdks");
+    let archive = fake_jdk_archive(temp_dir.path());
+
+    let result = Downloader::new().install_archive(&archive, Some(&"0".repeat(64)), &install_dir);
+
+    assert!(result.is_err());
+    assert!(!install_dir.join("OpenJDK21U-jdk_x64_linux").exists());
+}
+
+#[test]
+fn test_install_archive_without_jdk_leaves_nothing_behind() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let install_dir = temp_dir.path().join("jdks");
+    let docs = temp_dir.path().join("src").join("docs");
+    std::fs::create_dir_all(&docs).unwrap();
+    std::fs::write(docs.join("README"), "not a JDK").unwrap();
+
+    let archive = temp_dir.path().join("docs.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&archive).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_dir_all("docs", &docs).unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let result = Downloader::new().install_archive(&archive, None, &install_dir);
+
+    assert!(result.is_err());
+    assert!(!install_dir.join("docs").exists());
+    assert_eq!(
+        std::fs::read_dir(install_dir.join(STAGING_DIR))
+            .unwrap()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_clean_staging_removes_interrupted_installs() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let install_dir = temp_dir.path();
+    assert_eq!(Downloader::clean_staging(install_dir).unwrap(), 0);
+
+    let leftover = install_dir
+        .join(STAGING_DIR)
+        .join("Eclipse_Temurin-21.0.2-abc123");
+    std::fs::create_dir_all(leftover.join("bin")).unwrap();
+    std::fs::write(leftover.join("bin").join("java"), "").unwrap();
+    std::fs::create_dir_all(install_dir.join("Eclipse_Temurin-17.0.10")).unwrap();
+
+    // An install in progress elsewhere keeps its staging directory
+    let installing = jaman::lock::FileLock::shared(
+        &install_dir.join(LOCK_DIR).join("staging.lock"),
+        "the staging directory",
+    )
+    .unwrap();
+    assert_eq!(Downloader::clean_staging(install_dir).unwrap(), 0);
+    assert!(leftover.exists());
+    drop(installing);
+
+    assert_eq!(Downloader::clean_staging(install_dir).unwrap(), 1);
+    assert!(!install_dir.join(STAGING_DIR).exists());
+    assert!(install_dir.join("Eclipse_Temurin-17.0.10").exists());
+}
+
+#[tokio::test]
+async fn test_install_from_url() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive = fake_jdk_archive(temp_dir.path());
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artifacts/OpenJDK21U-jdk_x64_linux.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(std::fs::read(&archive).unwrap()))
+        .mount(&server)
+        .await;
+
+    let install_dir = temp_dir.path().join("jdks");
+    let jdk_root = Downloader::new()
+        .install_from_url(
+            &format!("{}/artifacts/OpenJDK21U-jdk_x64_linux.tar.gz", server.uri()),
+            Some(&sha256_of(&archive)),
+            &install_dir,
+        )
+        .await
+        .unwrap();
+
+    assert!(jdk_root.ends_with("OpenJDK21U-jdk_x64_linux/jdk-21.0.2+13"));
+}
+
+#[tokio::test]
+async fn test_download_names_archive_after_requested_url() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive = fake_jdk_archive(temp_dir.path());
+    let server = MockServer::start().await;
+    // Like GitHub release assets: the link redirects to a signed storage URL
+    Mock::given(method("GET"))
+        .and(path("/releases/OpenJDK21U-jdk_x64_linux.tar.gz"))
+        .respond_with(ResponseTemplate::new(302).insert_header(
+            "location",
+            format!(
+                "{}/assets/6f1c2a9e?X-Amz-Signature=abc&response-content-type=application%2Foctet-stream",
+                server.uri()
+            )
+            .as_str(),
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/assets/6f1c2a9e"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(std::fs::read(&archive).unwrap()))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/releases/OpenJDK21U-jdk_x64_linux.tar.gz", server.uri());
+    let checksum = sha256_of(&archive);
+    let cached = Downloader::new().with_cache_dir(&temp_dir.path().join("downloads"));
+    for (downloader, install_dir) in [(Downloader::new(), "direct"), (cached, "cached")] {
+        let jdk_root = downloader
+            .install_from_url(&url, Some(&checksum), &temp_dir.path().join(install_dir))
+            .await
+            .unwrap();
+        assert!(jdk_root.ends_with("OpenJDK21U-jdk_x64_linux/jdk-21.0.2+13"));
+    }
+}
+
+#[tokio::test]
+async fn test_download_names_unnamed_links_from_response() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive = fake_jdk_archive(temp_dir.path());
+    let server = MockServer::start().await;
+    // Like foojay's download endpoints, whose URLs name no archive
+    Mock::given(method("GET"))
+        .and(path("/ids/a1b2c3/redirect"))
+        .respond_with(ResponseTemplate::new(302).insert_header(
+            "location",
+            format!("{}/files/zulu21-linux_x64.tar.gz", server.uri()).as_str(),
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/files/zulu21-linux_x64.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(std::fs::read(&archive).unwrap()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/download"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header(
+                    "content-disposition",
+                    "attachment; filename=\"../corretto-21.tar.gz\"",
+                )
+                .set_body_bytes(std::fs::read(&archive).unwrap()),
+        )
+        .mount(&server)
+        .await;
+
+    for (link, name) in [
+        ("ids/a1b2c3/redirect", "zulu21-linux_x64"),
+        ("download", "corretto-21"),
+    ] {
+        let jdk_root = Downloader::new()
+            .install_from_url(
+                &format!("{}/{}", server.uri(), link),
+                None,
+                &temp_dir.path().join("jdks"),
+            )
+            .await
+            .unwrap();
+        assert!(jdk_root.ends_with(format!("{}/jdk-21.0.2+13", name)));
+    }
+}
+
+#[tokio::test]
+async fn test_feature_only_listing_installs_under_exact_version() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive = fake_jdk_archive(temp_dir.path());
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/21/latest/graalvm-jdk-21_linux-x64_bin.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(std::fs::read(&archive).unwrap()))
+        .mount(&server)
+        .await;
+
+    // Like Oracle GraalVM's "latest" links, listed by feature release only
+    let version = AvailableVersion {
+        version: "21".to_string(),
+        vendor: "Oracle GraalVM".to_string(),
+        is_lts: true,
+        architecture: "x64".to_string(),
+        download_url: format!(
+            "{}/21/latest/graalvm-jdk-21_linux-x64_bin.tar.gz",
+            server.uri()
+        ),
+        checksum: Some(sha256_of(&archive)),
+        javafx_bundled: false,
+        image_type: ImageType::Jdk,
+        channel: Channel::Ga,
+    };
+    assert!(version.is_feature_only());
+
+    let install_dir = temp_dir.path().join("jdks");
+    let jdk_root = Downloader::with_providers(Vec::new())
+        .download_and_install(&version, &install_dir)
+        .await
+        .unwrap();
+
+    // Named after JAVA_VERSION in the release file, so a later 21.0.x
+    // update installs next to it
+    assert!(jdk_root.ends_with("Oracle_GraalVM-21.0.2/jdk-21.0.2+13"));
+    assert!(!install_dir.join("Oracle_GraalVM-21").exists());
+}
+
+fn listed(version: &str, vendor: &str, javafx_bundled: bool) -> AvailableVersion {
+    AvailableVersion {
+        version: version.to_string(),
+        vendor: vendor.to_string(),
+        is_lts: true,
+        architecture: "x64".to_string(),
+        download_url: format!("https://example.com/{}-{}.tar.gz", vendor, version),
+        checksum: None,
+        javafx_bundled,
+        image_type: ImageType::Jdk,
+        channel: Channel::Ga,
+    }
+}
+
+#[test]
+fn test_distinct_builds_prefers_plain_unless_javafx_asked_for() {
+    let versions = vec![
+        listed("21.0.2+13", "Azul Zulu", true),
+        listed("21.0.2+13", "Azul Zulu", false),
+        listed("21.0.2+13", "Eclipse Temurin", false),
+    ];
+
+    let plain = AvailableVersion::distinct_builds(&versions, false);
+    assert_eq!(plain.len(), 2);
+    assert!(plain.iter().all(|v| !v.javafx_bundled));
+
+    let javafx = AvailableVersion::distinct_builds(&versions, true);
+    assert_eq!(javafx.len(), 1);
+    assert_eq!(javafx[0].vendor, "Azul Zulu");
+    assert!(javafx[0].javafx_bundled);
+}
+
+#[test]
+fn test_feature_only_listing_matches_installed_feature_release() {
+    let mut listing = listed("21", "Oracle GraalVM", false);
+    let installed = |version: &str, vendor: &str| {
+        JavaVersion::new(
+            version.to_string(),
+            vendor.to_string(),
+            std::path::PathBuf::from(format!("/test/{}-{}", vendor, version)),
+            true,
+            "x64".to_string(),
+            false,
+        )
+    };
+
+    assert!(listing.is_installed_as(&installed("21.0.2", "Oracle GraalVM")));
+    assert!(!listing.is_installed_as(&installed("22.0.1", "Oracle GraalVM")));
+    assert!(!listing.is_installed_as(&installed("21.0.2", "GraalVM CE")));
+
+    // The image type still has to match
+    listing.image_type = ImageType::Jre;
+    assert!(!listing.is_installed_as(&installed("21.0.2", "Oracle GraalVM")));
+
+    // Exact listings only match their exact version
+    let exact = listed("21.0.2+13", "Eclipse Temurin", false);
+    assert!(exact.is_installed_as(&installed("21.0.2+13", "Eclipse Temurin")));
+    assert!(!exact.is_installed_as(&installed("21.0.1+12", "Eclipse Temurin")));
+}
@@ -0,0 +1,217 @@
+use jaman::extract::{MAX_UNPACKED_SIZE, extract_archive};
+use std::io::Write;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// One tar entry. Names are written into the header verbatim, bypassing the
+/// checks `tar::Builder` applies, so hostile paths can be expressed.
+struct TarEntry<'a> {
+    name: &'a str,
+    entry_type: tar::EntryType,
+    link: Option<&'a str>,
+    data: &'a [u8],
+}
+
+fn file<'a>(name: &'a str, data: &'a [u8]) -> TarEntry<'a> {
+    TarEntry {
+        name,
+        entry_type: tar::EntryType::Regular,
+        link: None,
+        data,
+    }
+}
+
+fn link<'a>(name: &'a str, entry_type: tar::EntryType, target: &'a str) -> TarEntry<'a> {
+    TarEntry {
+        name,
+        entry_type,
+        link: Some(target),
+        data: b"",
+    }
+}
+
+fn write_tar_gz(dir: &Path, entries: &[TarEntry]) -> std::path::PathBuf {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    let mut builder = tar::Builder::new(encoder);
+
+    for entry in entries {
+        let mut header = tar::Header::new_gnu();
+        let name = &mut header.as_gnu_mut().unwrap().name;
+        name[..entry.name.len()].copy_from_slice(entry.name.as_bytes());
+        header.set_entry_type(entry.entry_type);
+        header.set_size(entry.data.len() as u64);
+        header.set_mode(0o644);
+        if let Some(target) = entry.link {
+            header.set_link_name(target).unwrap();
+        }
+        header.set_cksum();
+        builder.append(&header, entry.data).unwrap();
+    }
+
+    let path = dir.join("archive.tar.gz");
+    std::fs::write(&path, builder.into_inner().unwrap().finish().unwrap()).unwrap();
+    path
+}
+
+fn write_zip(
+    dir: &Path,
+    build: impl FnOnce(&mut zip::ZipWriter<std::fs::File>),
+) -> std::path::PathBuf {
+    let path = dir.join("archive.zip");
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    build(&mut writer);
+    writer.finish().unwrap();
+    path
+}
+
+/// A scratch directory holding the archive, and the destination inside it
+fn workspace() -> (TempDir, std::path::PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let dest = temp_dir.path().join("out").join("dest");
+    std::fs::create_dir_all(&dest).unwrap();
+    (temp_dir, dest)
+}
+
+#[test]
+fn test_extracts_regular_archive() {
+    let (temp_dir, dest) = workspace();
+    let archive = write_tar_gz(
+        temp_dir.path(),
+        &[
+            file("./jdk/bin/java", b"#!/bin/sh"),
+            file("jdk/legal/java.base/LICENSE", b"GPLv2"),
+            link(
+                "jdk/legal/java.xml/LICENSE",
+                tar::EntryType::Symlink,
+                "../java.base/LICENSE",
+            ),
+            link("jdk/bin/javac", tar::EntryType::Link, "jdk/bin/java"),
+        ],
+    );
+
+    extract_archive(&archive, &dest, MAX_UNPACKED_SIZE).unwrap();
+
+    assert!(dest.join("jdk/bin/java").is_file());
+    assert!(dest.join("jdk/bin/javac").is_file());
+    #[cfg(unix)]
+    assert_eq!(
+        std::fs::read(dest.join("jdk/legal/java.xml/LICENSE")).unwrap(),
+        b"GPLv2"
+    );
+}
+
+#[test]
+fn test_rejects_parent_dir_entry() {
+    let (temp_dir, dest) = workspace();
+    let archive = write_tar_gz(temp_dir.path(), &[file("jdk/../../evil.txt", b"pwned")]);
+
+    assert!(extract_archive(&archive, &dest, MAX_UNPACKED_SIZE).is_err());
+    assert!(!temp_dir.path().join("out/evil.txt").exists());
+}
+
+#[test]
+fn test_rejects_absolute_entry() {
+    let (temp_dir, dest) = workspace();
+    let target = temp_dir.path().join("evil.txt");
+    let name = target.to_str().unwrap();
+    let archive = write_tar_gz(temp_dir.path(), &[file(name, b"pwned")]);
+
+    assert!(extract_archive(&archive, &dest, MAX_UNPACKED_SIZE).is_err());
+    assert!(!target.exists());
+}
+
+#[test]
+fn test_rejects_escaping_symlinks() {
+    for target in ["../../../outside", "/etc", "bin/../../.."] {
+        let (temp_dir, dest) = workspace();
+        let archive = write_tar_gz(
+            temp_dir.path(),
+            &[link("jdk/lib", tar::EntryType::Symlink, target)],
+        );
+
+        let result = extract_archive(&archive, &dest, MAX_UNPACKED_SIZE);
+        assert!(result.is_err(), "symlink to {} was accepted", target);
+        assert!(std::fs::symlink_metadata(dest.join("jdk/lib")).is_err());
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_rejects_writing_through_symlink() {
+    let (temp_dir, dest) = workspace();
+    let archive = write_tar_gz(
+        temp_dir.path(),
+        &[
+            link("jdk/lib", tar::EntryType::Symlink, "."),
+            file("jdk/lib/libjvm.so", b"\x7fELF"),
+        ],
+    );
+
+    assert!(extract_archive(&archive, &dest, MAX_UNPACKED_SIZE).is_err());
+}
+
+#[test]
+fn test_rejects_escaping_hard_link() {
+    let (temp_dir, dest) = workspace();
+    std::fs::write(temp_dir.path().join("out/secret"), "secret").unwrap();
+    let archive = write_tar_gz(
+        temp_dir.path(),
+        &[link("jdk/secret", tar::EntryType::Link, "../secret")],
+    );
+
+    assert!(extract_archive(&archive, &dest, MAX_UNPACKED_SIZE).is_err());
+    assert!(!dest.join("jdk/secret").exists());
+}
+
+#[test]
+fn test_caps_unpacked_size() {
+    let (temp_dir, dest) = workspace();
+    let archive = write_tar_gz(
+        temp_dir.path(),
+        &[file("jdk/a", &[0u8; 600]), file("jdk/b", &[0u8; 600])],
+    );
+
+    let error = extract_archive(&archive, &dest, 1000).unwrap_err();
+    assert!(error.to_string().contains("more than 1000 bytes"));
+    assert!(extract_archive(&archive, TempDir::new().unwrap().path(), 1200).is_ok());
+}
+
+#[test]
+fn test_zip_rejects_parent_dir_entry() {
+    let (temp_dir, dest) = workspace();
+    let archive = write_zip(temp_dir.path(), |writer| {
+        writer
+            .start_file("../evil.txt", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"pwned").unwrap();
+    });
+
+    assert!(extract_archive(&archive, &dest, MAX_UNPACKED_SIZE).is_err());
+    assert!(!temp_dir.path().join("out/evil.txt").exists());
+}
+
+#[test]
+fn test_zip_rejects_escaping_symlink() {
+    let (temp_dir, dest) = workspace();
+    let archive = write_zip(temp_dir.path(), |writer| {
+        writer
+            .add_symlink("jdk/lib", "../../..", zip::write::FileOptions::default())
+            .unwrap();
+    });
+
+    assert!(extract_archive(&archive, &dest, MAX_UNPACKED_SIZE).is_err());
+    assert!(std::fs::symlink_metadata(dest.join("jdk/lib")).is_err());
+}
+
+#[test]
+fn test_zip_caps_unpacked_size() {
+    let (temp_dir, dest) = workspace();
+    let archive = write_zip(temp_dir.path(), |writer| {
+        writer
+            .start_file("jdk/lib/modules", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(&[0u8; 2048]).unwrap();
+    });
+
+    assert!(extract_archive(&archive, &dest, 1024).is_err());
+}
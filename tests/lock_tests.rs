@@ -0,0 +1,74 @@
+use jaman::lock::FileLock;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_exclusive_lock_times_out_while_held() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("locks").join("config.lock");
+
+    let held = FileLock::acquire(&path, "the config", false, Duration::ZERO).unwrap();
+
+    let started = Instant::now();
+    let error = FileLock::acquire(&path, "the config", false, Duration::from_millis(300))
+        .unwrap_err()
+        .to_string();
+    assert!(started.elapsed() >= Duration::from_millis(300));
+    assert!(error.contains("Timed out"));
+    assert!(error.contains("the config"));
+
+    drop(held);
+    assert!(FileLock::acquire(&path, "the config", false, Duration::ZERO).is_ok());
+}
+
+#[test]
+fn test_shared_locks_coexist() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("staging.lock");
+
+    let first = FileLock::acquire(&path, "staging", true, Duration::ZERO).unwrap();
+    let second = FileLock::acquire(&path, "staging", true, Duration::ZERO).unwrap();
+    assert!(FileLock::try_exclusive(&path).unwrap().is_none());
+
+    drop(first);
+    drop(second);
+    let exclusive = FileLock::try_exclusive(&path).unwrap();
+    assert!(exclusive.is_some());
+    assert!(FileLock::acquire(&path, "staging", true, Duration::ZERO).is_err());
+}
+
+#[test]
+fn test_lock_waits_for_release() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("install.lock");
+
+    let held = FileLock::acquire(&path, "the install", false, Duration::ZERO).unwrap();
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(200));
+        drop(held);
+    });
+
+    assert!(FileLock::acquire(&path, "the install", false, Duration::from_secs(10)).is_ok());
+    releaser.join().unwrap();
+}
+
+#[tokio::test]
+async fn test_async_lock_wait_leaves_runtime_free() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("download.lock");
+
+    // The test runtime has a single thread: the holder only gets to release
+    // the lock if waiting for it does not block that thread
+    let held = FileLock::acquire(&path, "the download", false, Duration::ZERO).unwrap();
+    let releaser = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        drop(held);
+    });
+
+    std::env::set_var("JAMAN_LOCK_TIMEOUT", "10");
+    assert!(
+        FileLock::exclusive_async(&path, "the download")
+            .await
+            .is_ok()
+    );
+    releaser.await.unwrap();
+}
@@ -0,0 +1,209 @@
+use jaman::config::{Config, JavaVersion};
+use jaman::project::{ProjectVersion, effective_version};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn config_with(versions: &[(&str, &str)]) -> Config {
+    let mut config = Config::new(PathBuf::from("/test"), PathBuf::from("/test/downloads"));
+    for (version, vendor) in versions {
+        config.add_version(JavaVersion::new(
+            version.to_string(),
+            vendor.to_string(),
+            PathBuf::from("/test").join(format!("{}-{}", vendor, version)),
+            true,
+            "x64".to_string(),
+            false,
+        ));
+    }
+    config
+}
+
+#[test]
+fn test_find_walks_up_to_nearest_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let service = root.join("services").join("billing");
+    fs::create_dir_all(service.join("src")).unwrap();
+    fs::write(root.join(".java-version"), "21\n").unwrap();
+    fs::write(
+        service.join(".java-version"),
+        "# pinned for the billing service\n11\n",
+    )
+    .unwrap();
+
+    let found = ProjectVersion::find(&service.join("src")).unwrap().unwrap();
+    assert_eq!(found.spec, "11");
+    assert_eq!(found.file, service.join(".java-version"));
+
+    let found = ProjectVersion::find(&root.join("services"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.spec, "21");
+}
+
+#[test]
+fn test_reads_sdkmanrc() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join(".sdkmanrc");
+
+    fs::write(&file, "# Enable auto-env\njava=21.0.2-tem\nmaven=3.9.6\n").unwrap();
+    assert_eq!(
+        ProjectVersion::read(&file).unwrap().unwrap().spec,
+        "temurin@21.0.2"
+    );
+
+    fs::write(&file, "java=17.0.10.fx-zulu\n").unwrap();
+    let fx = ProjectVersion::read(&file).unwrap().unwrap();
+    assert_eq!(fx.spec, "zulu@17.0.10");
+    assert!(fx.javafx);
+
+    fs::write(&file, "java=17.0.2-open\n").unwrap();
+    assert_eq!(ProjectVersion::read(&file).unwrap().unwrap().spec, "17.0.2");
+
+    fs::write(&file, "gradle=8.5\n").unwrap();
+    assert_eq!(ProjectVersion::read(&file).unwrap(), None);
+}
+
+#[test]
+fn test_reads_tool_versions() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join(".tool-versions");
+
+    fs::write(&file, "nodejs 20.11.0\njava temurin-21.0.2+13.0.LTS\n").unwrap();
+    assert_eq!(
+        ProjectVersion::read(&file).unwrap().unwrap().spec,
+        "temurin@21.0.2+13.0.LTS"
+    );
+
+    fs::write(&file, "java openjdk-17 # team default\n").unwrap();
+    assert_eq!(ProjectVersion::read(&file).unwrap().unwrap().spec, "17");
+
+    // Corretto's own version numbers become Java versions
+    fs::write(&file, "java corretto-11.0.22.7.1\n").unwrap();
+    assert_eq!(
+        ProjectVersion::read(&file).unwrap().unwrap().spec,
+        "corretto@11.0.22+7"
+    );
+    fs::write(&file, "java corretto-8.402.08.1\n").unwrap();
+    assert_eq!(
+        ProjectVersion::read(&file).unwrap().unwrap().spec,
+        "corretto@8.0.402+8"
+    );
+
+    // Azul's product versions cannot be mapped to Java versions
+    fs::write(&file, "java zulu-21.32.17\n").unwrap();
+    let error = format!("{:#}", ProjectVersion::read(&file).unwrap_err());
+    assert!(error.contains("Azul Zulu version number"));
+    fs::write(&file, "java zulu-21.0.2\n").unwrap();
+    assert_eq!(
+        ProjectVersion::read(&file).unwrap().unwrap().spec,
+        "zulu@21.0.2"
+    );
+
+    fs::write(&file, "python 3.12.1\n").unwrap();
+    assert_eq!(ProjectVersion::read(&file).unwrap(), None);
+}
+
+#[test]
+fn test_java_version_takes_precedence_in_a_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tool-versions"),
+        "java temurin-17.0.10+7\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join(".sdkmanrc"), "java=11.0.22-tem\n").unwrap();
+    assert_eq!(
+        ProjectVersion::find(temp_dir.path()).unwrap().unwrap().spec,
+        "temurin@11.0.22"
+    );
+
+    fs::write(temp_dir.path().join(".java-version"), "21\n").unwrap();
+    assert_eq!(
+        ProjectVersion::find(temp_dir.path()).unwrap().unwrap().spec,
+        "21"
+    );
+
+    // A file that does not name Java is skipped in favour of a parent's
+    let nested = temp_dir.path().join("tools");
+    fs::create_dir(&nested).unwrap();
+    fs::write(nested.join(".tool-versions"), "nodejs 20.11.0\n").unwrap();
+    assert_eq!(ProjectVersion::find(&nested).unwrap().unwrap().spec, "21");
+}
+
+#[test]
+fn test_effective_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = config_with(&[
+        ("11.0.22", "Eclipse Temurin"),
+        ("17.0.10", "Eclipse Temurin"),
+        ("21.0.2", "Amazon Corretto"),
+    ]);
+
+    // Without a project file the global version is in effect
+    assert!(
+        effective_version(&config, temp_dir.path())
+            .unwrap()
+            .is_none()
+    );
+    config.set_active("corretto-21.0.2-x64").unwrap();
+    let (version, source) = effective_version(&config, temp_dir.path())
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.version, "21.0.2");
+    assert!(source.is_none());
+
+    fs::write(temp_dir.path().join(".java-version"), "17\n").unwrap();
+    let (version, source) = effective_version(&config, temp_dir.path())
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.version, "17.0.10");
+    assert_eq!(source.unwrap().file, temp_dir.path().join(".java-version"));
+
+    // Installation IDs work as well
+    fs::write(
+        temp_dir.path().join(".java-version"),
+        "temurin-11.0.22-x64\n",
+    )
+    .unwrap();
+    let (version, _) = effective_version(&config, temp_dir.path())
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.version, "11.0.22");
+
+    fs::write(temp_dir.path().join(".java-version"), "8\n").unwrap();
+    let error = effective_version(&config, temp_dir.path()).unwrap_err();
+    assert!(format!("{:#}", error).contains("jaman install 8"));
+}
+
+#[test]
+fn test_javafx_pin_needs_javafx_build() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join(".sdkmanrc"), "java=21.0.2.fx-zulu\n").unwrap();
+    let mut config = config_with(&[("21.0.2", "Azul Zulu")]);
+
+    // Only a build without JavaFX is installed
+    let error = effective_version(&config, temp_dir.path()).unwrap_err();
+    let error = format!("{:#}", error);
+    assert!(error.contains("with JavaFX"));
+    assert!(error.contains("do not bundle JavaFX"));
+    assert!(error.contains("jaman install --javafx zulu@21.0.2"));
+
+    let mut fx = JavaVersion::new(
+        "21.0.2".to_string(),
+        "Azul Zulu".to_string(),
+        PathBuf::from("/test/Azul Zulu-21.0.2-fx"),
+        true,
+        "x64".to_string(),
+        false,
+    );
+    fx.javafx_bundled = true;
+    config.add_version(fx);
+
+    let (version, source) = effective_version(&config, temp_dir.path())
+        .unwrap()
+        .unwrap();
+    assert!(version.javafx_bundled);
+    assert!(source.unwrap().javafx);
+}
@@ -0,0 +1,1205 @@
+use async_trait::async_trait;
+use jaman::cache::CacheMode;
+use jaman::config::{Channel, ImageType, ReleaseSelection};
+use jaman::downloader::{AvailableVersion, Downloader};
+use jaman::providers::{
+    CorrettoProvider, FoojayProvider, GraalVmCeProvider, OracleGraalVmProvider, Provider, Release,
+    Target, TemurinProvider, ZuluProvider, is_lts_release,
+};
+use jaman::query::VersionQuery;
+use jaman::retry::RetryPolicy;
+use reqwest::Client;
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mock_adoptium() -> MockServer {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/info/available_releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "available_releases": [17, 21, 22],
+            "available_lts_releases": [17, 21],
+        })))
+        .mount(&server)
+        .await;
+
+    for (major, semver) in [(17, "17.0.10+7"), (21, "21.0.2+13"), (22, "22.0.1+8")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v3/assets/latest/{}/hotspot", major)))
+            .and(query_param("architecture", "x64"))
+            .and(query_param("image_type", "jdk"))
+            .and(query_param("os", "linux"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "binary": {
+                    "package": {
+                        "link": format!("https://example.com/jdk-{}.tar.gz", semver),
+                        "checksum": "abc123",
+                    }
+                },
+                "version": { "semver": semver },
+            }])))
+            .mount(&server)
+            .await;
+    }
+
+    server
+}
+
+/// Provider with fixed data, used to check how the downloader merges registries
+struct StaticProvider {
+    vendor: &'static str,
+}
+
+#[async_trait]
+impl Provider for StaticProvider {
+    fn vendor(&self) -> &str {
+        self.vendor
+    }
+
+    async fn list_releases(&self, _client: &Client) -> anyhow::Result<Vec<Release>> {
+        Ok(vec![Release {
+            major: 21,
+            is_lts: true,
+        }])
+    }
+
+    async fn resolve_assets(
+        &self,
+        _client: &Client,
+        release: &Release,
+        target: &Target,
+    ) -> anyhow::Result<Vec<AvailableVersion>> {
+        Ok(vec![AvailableVersion {
+            version: format!("{}.0.2", release.major),
+            vendor: self.vendor.to_string(),
+            is_lts: release.is_lts,
+            architecture: target.arch.clone(),
+            download_url: "https://example.com/jdk.tar.gz".to_string(),
+            checksum: None,
+            javafx_bundled: false,
+            image_type: target.image_type,
+            channel: Channel::Ga,
+        }])
+    }
+}
+
+#[tokio::test]
+async fn test_temurin_list_releases() {
+    let server = mock_adoptium().await;
+    let provider = TemurinProvider::with_base_url(&server.uri());
+
+    let releases = provider.list_releases(&Client::new()).await.unwrap();
+
+    assert_eq!(releases.len(), 3);
+    assert_eq!(
+        releases[1],
+        Release {
+            major: 21,
+            is_lts: true
+        }
+    );
+    assert!(!releases[2].is_lts);
+}
+
+#[tokio::test]
+async fn test_temurin_resolve_assets() {
+    let server = mock_adoptium().await;
+    let provider = TemurinProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 21,
+        is_lts: true,
+    };
+
+    let assets = provider
+        .resolve_assets(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].version, "21.0.2+13");
+    assert_eq!(assets[0].vendor, "Eclipse Temurin");
+    assert_eq!(assets[0].architecture, "x64");
+    assert_eq!(assets[0].checksum.as_deref(), Some("abc123"));
+    assert!(assets[0].is_lts);
+}
+
+#[tokio::test]
+async fn test_temurin_list_releases_server_error() {
+    let server = MockServer::start().await;
+    let provider = TemurinProvider::with_base_url(&server.uri());
+
+    let result = provider.list_releases(&Client::new()).await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_default_fetch_checksum_uses_listing() {
+    let provider = StaticProvider { vendor: "Static" };
+    let version = AvailableVersion {
+        version: "21.0.2".to_string(),
+        vendor: "Static".to_string(),
+        is_lts: true,
+        architecture: "x64".to_string(),
+        download_url: "https://example.com/jdk.tar.gz".to_string(),
+        checksum: Some("def456".to_string()),
+        javafx_bundled: false,
+        image_type: ImageType::Jdk,
+        channel: Channel::Ga,
+    };
+
+    let checksum = provider
+        .fetch_checksum(&Client::new(), &version)
+        .await
+        .unwrap();
+
+    assert_eq!(checksum.as_deref(), Some("def456"));
+}
+
+#[tokio::test]
+async fn test_downloader_merges_providers() {
+    let server = mock_adoptium().await;
+    let downloader = Downloader::with_providers(vec![
+        Box::new(TemurinProvider::with_base_url(&server.uri())),
+        Box::new(StaticProvider { vendor: "Static" }),
+    ])
+    .with_target(Target::new("linux", "x64"));
+
+    let versions = downloader
+        .fetch_available_versions()
+        .await
+        .unwrap()
+        .versions;
+
+    assert_eq!(versions.len(), 4);
+    assert_eq!(
+        versions
+            .iter()
+            .filter(|v| v.vendor == "Eclipse Temurin")
+            .count(),
+        3
+    );
+    assert!(versions.iter().any(|v| v.vendor == "Static"));
+}
+
+#[tokio::test]
+async fn test_downloader_register_provider() {
+    let mut downloader = Downloader::with_providers(Vec::new());
+    downloader.register(Box::new(StaticProvider { vendor: "Static" }));
+
+    let versions = downloader
+        .fetch_available_versions()
+        .await
+        .unwrap()
+        .versions;
+
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].version, "21.0.2");
+}
+
+#[tokio::test]
+async fn test_downloader_caches_listing() {
+    let server = mock_adoptium().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let downloader = || {
+        Downloader::with_providers(vec![Box::new(TemurinProvider::with_base_url(
+            &server.uri(),
+        ))])
+        .with_target(Target::new("linux", "x64"))
+        .with_metadata_cache(temp_dir.path(), chrono::Duration::hours(24))
+    };
+    let requests = || async { server.received_requests().await.unwrap().len() };
+
+    let first = downloader().fetch_available_versions().await.unwrap();
+    assert!(first.cached_at.is_none());
+    let fetched = requests().await;
+
+    let second = downloader().fetch_available_versions().await.unwrap();
+    assert!(second.cached_at.is_some());
+    assert_eq!(second.versions.len(), first.versions.len());
+    assert_eq!(requests().await, fetched);
+
+    let refreshed = downloader()
+        .with_cache_mode(CacheMode::Refresh)
+        .fetch_available_versions()
+        .await
+        .unwrap();
+    assert!(refreshed.cached_at.is_none());
+    assert!(requests().await > fetched);
+}
+
+#[tokio::test]
+async fn test_downloader_offline_needs_cached_listing() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let downloader = |mode| {
+        Downloader::with_providers(vec![Box::new(StaticProvider { vendor: "Static" })])
+            .with_metadata_cache(temp_dir.path(), chrono::Duration::zero())
+            .with_cache_mode(mode)
+    };
+
+    assert!(
+        downloader(CacheMode::Offline)
+            .fetch_available_versions()
+            .await
+            .is_err()
+    );
+
+    downloader(CacheMode::Default)
+        .fetch_available_versions()
+        .await
+        .unwrap();
+
+    // Offline mode ignores the TTL
+    let report = downloader(CacheMode::Offline)
+        .fetch_available_versions()
+        .await
+        .unwrap();
+    assert_eq!(report.versions.len(), 1);
+    assert!(report.cached_at.is_some());
+}
+
+async fn mock_corretto() -> MockServer {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/indexmap_with_checksum.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "linux": {
+                "x64": {
+                    "jdk": {
+                        "21": {
+                            "tar.gz": {
+                                "resource": "/downloads/resources/21.0.2.13.1/amazon-corretto-21.0.2.13.1-linux-x64.tar.gz",
+                                "checksum": "0123456789abcdef0123456789abcdef",
+                                "checksum_sha256": "aaaabbbbccccdddd",
+                            }
+                        },
+                        "8": {
+                            "tar.gz": {
+                                "resource": "/downloads/resources/8.402.08.1/amazon-corretto-8.402.08.1-linux-x64.tar.gz",
+                                "checksum": "0123456789abcdef0123456789abcdef",
+                                "checksum_sha256": "eeeeffff00001111",
+                            }
+                        },
+                        "22": {
+                            "tar.gz": {
+                                "resource": "/downloads/resources/22.0.0.37.1/amazon-corretto-22.0.0.37.1-linux-x64.tar.gz",
+                                "checksum": "0123456789abcdef0123456789abcdef",
+                                "checksum_sha256": "2222333344445555",
+                            }
+                        }
+                    }
+                }
+            },
+            "macos": {
+                "aarch64": {
+                    "jdk": {
+                        "17": {
+                            "tar.gz": {
+                                "resource": "/downloads/resources/17.0.10.7.1/amazon-corretto-17.0.10.7.1-macosx-aarch64.tar.gz",
+                                "checksum": "0123456789abcdef0123456789abcdef",
+                                "checksum_sha256": "6666777788889999",
+                            }
+                        }
+                    }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    server
+}
+
+fn corretto_provider(server: &MockServer) -> CorrettoProvider {
+    CorrettoProvider::with_urls(
+        &format!("{}/indexmap_with_checksum.json", server.uri()),
+        "https://corretto.aws",
+    )
+}
+
+#[tokio::test]
+async fn test_corretto_list_releases() {
+    let server = mock_corretto().await;
+    let provider = corretto_provider(&server);
+
+    let releases = provider.list_releases(&Client::new()).await.unwrap();
+    let majors: Vec<u32> = releases.iter().map(|r| r.major).collect();
+
+    assert_eq!(majors, vec![8, 17, 21, 22]);
+    assert!(releases.iter().find(|r| r.major == 21).unwrap().is_lts);
+    assert!(!releases.iter().find(|r| r.major == 22).unwrap().is_lts);
+}
+
+#[tokio::test]
+async fn test_corretto_resolve_assets() {
+    let server = mock_corretto().await;
+    let provider = corretto_provider(&server);
+    let client = Client::new();
+    let target = Target::new("linux", "x64");
+
+    // The index is fetched once and reused for every release
+    let releases = provider.list_releases(&client).await.unwrap();
+    let release = releases.iter().find(|r| r.major == 21).unwrap();
+    let assets = provider
+        .resolve_assets(&client, release, &target)
+        .await
+        .unwrap();
+
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].version, "21.0.2+13");
+    assert_eq!(assets[0].vendor, "Amazon Corretto");
+    assert_eq!(assets[0].architecture, "x64");
+    assert_eq!(assets[0].checksum.as_deref(), Some("aaaabbbbccccdddd"));
+    assert_eq!(
+        assets[0].download_url,
+        "https://corretto.aws/downloads/resources/21.0.2.13.1/amazon-corretto-21.0.2.13.1-linux-x64.tar.gz"
+    );
+
+    let release = Release {
+        major: 8,
+        is_lts: true,
+    };
+    let assets = provider
+        .resolve_assets(&client, &release, &target)
+        .await
+        .unwrap();
+    assert_eq!(assets[0].version, "8.0.402+8");
+}
+
+#[tokio::test]
+async fn test_corretto_resolve_assets_other_platform() {
+    let server = mock_corretto().await;
+    let provider = corretto_provider(&server);
+    let release = Release {
+        major: 17,
+        is_lts: true,
+    };
+
+    let assets = provider
+        .resolve_assets(&Client::new(), &release, &Target::new("mac", "aarch64"))
+        .await
+        .unwrap();
+    assert_eq!(assets[0].version, "17.0.10+7");
+
+    let assets = provider
+        .resolve_assets(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+    assert!(assets.is_empty());
+}
+
+#[test]
+fn test_is_lts_release() {
+    for major in [8, 11, 17, 21, 25] {
+        assert!(is_lts_release(major), "{} should be LTS", major);
+    }
+    for major in [9, 10, 12, 16, 18, 22, 23, 24] {
+        assert!(!is_lts_release(major), "{} should not be LTS", major);
+    }
+}
+
+fn zulu_package(java_version: [u32; 3], build: u32, fx: bool) -> serde_json::Value {
+    let name = if fx { "fx-jdk" } else { "jdk" };
+    json!({
+        "package_uuid": format!("uuid-{}-{}", java_version[0], name),
+        "name": format!("zulu-ca-{}{}-linux_x64.tar.gz", name, java_version[0]),
+        "java_version": java_version,
+        "openjdk_build_number": build,
+        "latest": true,
+        "download_url": format!("https://cdn.azul.com/zulu/bin/zulu-ca-{}{}.tar.gz", name, java_version[0]),
+        "sha256_hash": format!("sha-{}-{}", java_version[0], name),
+        "javafx_bundled": fx,
+        "support_term": if java_version[0] == 21 { "lts" } else { "sts" },
+    })
+}
+
+async fn mock_zulu() -> MockServer {
+    let server = MockServer::start().await;
+
+    for fx in [false, true] {
+        Mock::given(method("GET"))
+            .and(path("/metadata/v1/zulu/packages/"))
+            .and(query_param("java_version", "21"))
+            .and(query_param("os", "linux_glibc"))
+            .and(query_param("arch", "x64"))
+            .and(query_param("archive_type", "tar.gz"))
+            .and(query_param("javafx_bundled", fx.to_string()))
+            .and(query_param("crac_supported", "false"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!([zulu_package([21, 0, 2], 13, fx)])),
+            )
+            .mount(&server)
+            .await;
+    }
+
+    Mock::given(method("GET"))
+        .and(path("/metadata/v1/zulu/packages/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            zulu_package([22, 0, 1], 8, false),
+            zulu_package([21, 0, 2], 13, false),
+            zulu_package([21, 0, 2], 13, true),
+        ])))
+        .mount(&server)
+        .await;
+
+    server
+}
+
+#[tokio::test]
+async fn test_zulu_list_releases() {
+    let server = mock_zulu().await;
+    let provider = ZuluProvider::with_base_url(&server.uri());
+
+    let releases = provider.list_releases(&Client::new()).await.unwrap();
+
+    assert_eq!(
+        releases,
+        vec![
+            Release {
+                major: 21,
+                is_lts: true
+            },
+            Release {
+                major: 22,
+                is_lts: false
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_zulu_resolve_assets_includes_javafx_bundles() {
+    let server = mock_zulu().await;
+    let provider = ZuluProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 21,
+        is_lts: true,
+    };
+
+    let assets = provider
+        .resolve_assets(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(assets.len(), 2);
+    assert!(assets.iter().all(|v| v.version == "21.0.2+13"));
+    assert!(assets.iter().all(|v| v.vendor == "Azul Zulu"));
+
+    let plain = assets.iter().find(|v| !v.javafx_bundled).unwrap();
+    assert_eq!(plain.checksum.as_deref(), Some("sha-21-jdk"));
+
+    let fx = assets.iter().find(|v| v.javafx_bundled).unwrap();
+    assert_eq!(fx.checksum.as_deref(), Some("sha-21-fx-jdk"));
+    assert!(fx.download_url.contains("fx-jdk"));
+}
+
+#[tokio::test]
+async fn test_zulu_resolve_assets_skips_musl_crac_and_headless_packages() {
+    let server = MockServer::start().await;
+    let mut headless = zulu_package([21, 0, 2], 13, false);
+    headless["java_package_features"] = json!(["headless"]);
+    headless["sha256_hash"] = json!("sha-21-headless");
+    Mock::given(method("GET"))
+        .and(path("/metadata/v1/zulu/packages/"))
+        .and(query_param("os", "linux_glibc"))
+        .and(query_param("crac_supported", "false"))
+        .and(query_param("java_package_type", "jdk"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([zulu_package([21, 0, 2], 13, false), headless])),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+    let provider = ZuluProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 21,
+        is_lts: true,
+    };
+
+    let assets = provider
+        .resolve_assets(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    // Both requests (plain and JavaFX) only list the full glibc JDK
+    assert_eq!(assets.len(), 2);
+    assert!(
+        assets
+            .iter()
+            .all(|v| v.checksum.as_deref() == Some("sha-21-jdk"))
+    );
+}
+
+async fn mock_graalvm() -> MockServer {
+    let server = MockServer::start().await;
+    let asset = |version: &str, platform: &str, ext: &str| {
+        let name = format!("graalvm-community-jdk-{}_{}_bin.{}", version, platform, ext);
+        json!({
+            "name": name,
+            "browser_download_url": format!("{}/download/{}", server.uri(), name),
+        })
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "tag_name": "jdk-23.0.0-ea.01",
+                "prerelease": true,
+                "assets": [],
+            },
+            {
+                "tag_name": "jdk-21.0.2",
+                "prerelease": false,
+                "assets": [
+                    asset("21.0.2", "linux-x64", "tar.gz"),
+                    asset("21.0.2", "linux-x64", "tar.gz.sha256"),
+                    asset("21.0.2", "windows-x64", "zip"),
+                ],
+            },
+            {
+                "tag_name": "jdk-21.0.1",
+                "prerelease": false,
+                "assets": [asset("21.0.1", "linux-x64", "tar.gz")],
+            },
+            {
+                "tag_name": "jdk-17.0.9",
+                "prerelease": false,
+                "assets": [asset("17.0.9", "linux-x64", "tar.gz")],
+            },
+            {
+                "tag_name": "vm-22.3.3",
+                "prerelease": false,
+                "assets": [],
+            },
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/download/graalvm-community-jdk-21.0.2_linux-x64_bin.tar.gz.sha256",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("0a1b2c3d\n"))
+        .mount(&server)
+        .await;
+
+    server
+}
+
+#[tokio::test]
+async fn test_graalvm_ce_list_and_resolve() {
+    let server = mock_graalvm().await;
+    let provider = GraalVmCeProvider::with_releases_url(&format!("{}/releases", server.uri()));
+    let client = Client::new();
+
+    let releases = provider.list_releases(&client).await.unwrap();
+    let majors: Vec<u32> = releases.iter().map(|r| r.major).collect();
+    assert_eq!(majors, vec![17, 21]);
+
+    let release = releases.iter().find(|r| r.major == 21).unwrap();
+    let assets = provider
+        .resolve_assets(&client, release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].version, "21.0.2");
+    assert_eq!(assets[0].vendor, "GraalVM CE");
+    assert!(assets[0].is_lts);
+    assert!(assets[0].checksum.is_none());
+    assert!(
+        assets[0]
+            .download_url
+            .ends_with("graalvm-community-jdk-21.0.2_linux-x64_bin.tar.gz")
+    );
+
+    let checksum = provider.fetch_checksum(&client, &assets[0]).await.unwrap();
+    assert_eq!(checksum.as_deref(), Some("0a1b2c3d"));
+}
+
+#[tokio::test]
+async fn test_oracle_graalvm_resolve_assets() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/21/latest/graalvm-jdk-21_macos-aarch64_bin.tar.gz.sha256",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("feedface  graalvm-jdk-21.tar.gz"))
+        .mount(&server)
+        .await;
+
+    let provider = OracleGraalVmProvider::with_download_base(&server.uri());
+    let client = Client::new();
+
+    let releases = provider.list_releases(&client).await.unwrap();
+    assert!(releases.iter().all(|r| r.is_lts));
+
+    let release = releases.iter().find(|r| r.major == 21).unwrap();
+    let assets = provider
+        .resolve_assets(&client, release, &Target::new("mac", "aarch64"))
+        .await
+        .unwrap();
+
+    assert_eq!(assets[0].vendor, "Oracle GraalVM");
+    assert_eq!(
+        assets[0].download_url,
+        format!(
+            "{}/21/latest/graalvm-jdk-21_macos-aarch64_bin.tar.gz",
+            server.uri()
+        )
+    );
+
+    let checksum = provider.fetch_checksum(&client, &assets[0]).await.unwrap();
+    assert_eq!(checksum.as_deref(), Some("feedface"));
+}
+
+/// Serve a recorded Disco API response, with links rewritten to the mock server
+fn foojay_fixture(server: &MockServer, fixture: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "application/json")
+        .set_body_string(fixture.replace("https://api.foojay.io", &server.uri()))
+}
+
+async fn mock_foojay() -> MockServer {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/disco/v3.0/major_versions"))
+        .respond_with(foojay_fixture(
+            &server,
+            include_str!("fixtures/foojay/major_versions.json"),
+        ))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/disco/v3.0/packages"))
+        .and(query_param("version", "21"))
+        .and(query_param("operating_system", "linux"))
+        .and(query_param("architecture", "x64"))
+        .and(query_param("lib_c_type", "glibc"))
+        .and(query_param("distribution", "graalvm_community"))
+        .and(query_param("distribution", "graalvm"))
+        .respond_with(foojay_fixture(
+            &server,
+            include_str!("fixtures/foojay/packages_21_linux_x64.json"),
+        ))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/disco/v3.0/ids/4c81d2a9ab4e0e7fcf1d0a4bd6b6a3c8"))
+        .respond_with(foojay_fixture(
+            &server,
+            include_str!("fixtures/foojay/ids_4c81d2a9ab4e0e7fcf1d0a4bd6b6a3c8.json"),
+        ))
+        .mount(&server)
+        .await;
+
+    server
+}
+
+#[tokio::test]
+async fn test_foojay_list_releases() {
+    let server = mock_foojay().await;
+    let provider = FoojayProvider::with_base_url(&server.uri());
+
+    let releases = provider.list_releases(&Client::new()).await.unwrap();
+
+    let majors: Vec<(u32, bool)> = releases.iter().map(|r| (r.major, r.is_lts)).collect();
+    assert_eq!(majors, vec![(17, true), (21, true), (22, false)]);
+}
+
+#[tokio::test]
+async fn test_foojay_resolve_assets() {
+    let server = mock_foojay().await;
+    let provider = FoojayProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 21,
+        is_lts: true,
+    };
+
+    let assets = provider
+        .resolve_assets(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    // The Oracle package is not directly downloadable and is skipped
+    assert_eq!(assets.len(), 2);
+
+    let temurin = assets
+        .iter()
+        .find(|v| v.vendor == "Eclipse Temurin")
+        .unwrap();
+    assert_eq!(temurin.version, "21.0.2+13");
+    assert!(temurin.is_lts);
+    assert!(!temurin.javafx_bundled);
+    assert!(provider.provides(temurin));
+
+    let liberica = assets
+        .iter()
+        .find(|v| v.vendor == "BellSoft Liberica")
+        .unwrap();
+    assert_eq!(liberica.version, "21.0.2+14");
+    assert!(liberica.javafx_bundled);
+}
+
+#[tokio::test]
+async fn test_foojay_fetch_checksum() {
+    let server = mock_foojay().await;
+    let provider = FoojayProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 21,
+        is_lts: true,
+    };
+    let client = Client::new();
+
+    let assets = provider
+        .resolve_assets(&client, &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+    let temurin = assets
+        .iter()
+        .find(|v| v.vendor == "Eclipse Temurin")
+        .unwrap();
+
+    let checksum = provider.fetch_checksum(&client, temurin).await.unwrap();
+    assert_eq!(
+        checksum.as_deref(),
+        Some("454bebb2c9fe48d981341461ffb6bf1017c7b7c6e15c6b0c29b959194ba3aaa5")
+    );
+}
+
+#[test]
+fn test_foojay_vendor_names_match_direct_providers() {
+    assert_eq!(
+        FoojayProvider::vendor_name("temurin"),
+        TemurinProvider::new().vendor()
+    );
+    assert_eq!(
+        FoojayProvider::vendor_name("corretto"),
+        CorrettoProvider::new().vendor()
+    );
+    assert_eq!(
+        FoojayProvider::vendor_name("zulu"),
+        ZuluProvider::new().vendor()
+    );
+    assert_eq!(
+        FoojayProvider::vendor_name("unknown_vendor"),
+        "unknown_vendor"
+    );
+}
+
+fn adoptium_release(update: u32, build: u32) -> serde_json::Value {
+    let semver = format!("17.0.{}+{}", update, build);
+    json!({
+        "release_name": format!("jdk-{}", semver),
+        "binaries": [{
+            "package": {
+                "link": format!("https://example.com/OpenJDK17U-jdk_x64_linux_hotspot_17.0.{}_{}.tar.gz", update, build),
+                "checksum": format!("sha-{}", semver),
+            }
+        }],
+        "version_data": { "semver": semver },
+    })
+}
+
+#[tokio::test]
+async fn test_temurin_list_patch_releases_paginates() {
+    let server = mock_adoptium().await;
+
+    // A full first page, then a short second page ends the listing
+    let first_page: Vec<_> = (0..20).map(|i| adoptium_release(30 - i, 1)).collect();
+    let second_page = vec![
+        adoptium_release(7, 7),
+        adoptium_release(6, 10),
+        adoptium_release(5, 8),
+    ];
+
+    for (page, releases) in [(0, first_page), (1, second_page)] {
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/feature_releases/17/ga"))
+            .and(query_param("page", page.to_string()))
+            .and(query_param("os", "linux"))
+            .and(query_param("architecture", "x64"))
+            .and(query_param("image_type", "jdk"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(releases))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let provider = TemurinProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 17,
+        is_lts: true,
+    };
+
+    let versions = provider
+        .list_patch_releases(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(versions.len(), 23);
+    assert_eq!(versions[0].version, "17.0.30+1");
+
+    let query: VersionQuery = "17.0.6".parse().unwrap();
+    let pinned = query.select(&versions).unwrap().unwrap();
+    assert_eq!(pinned.version, "17.0.6+10");
+    assert_eq!(pinned.checksum.as_deref(), Some("sha-17.0.6+10"));
+}
+
+#[tokio::test]
+async fn test_temurin_list_patch_releases_stops_on_not_found() {
+    let server = MockServer::start().await;
+    let first_page: Vec<_> = (0..20).map(|i| adoptium_release(20 - i, 1)).collect();
+
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/feature_releases/17/ga"))
+        .and(query_param("page", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/feature_releases/17/ga"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let provider = TemurinProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 17,
+        is_lts: true,
+    };
+
+    let versions = provider
+        .list_patch_releases(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(versions.len(), 20);
+}
+
+#[tokio::test]
+async fn test_default_list_patch_releases_offers_latest_only() {
+    let provider = StaticProvider { vendor: "Static" };
+    let release = Release {
+        major: 21,
+        is_lts: true,
+    };
+
+    let versions = provider
+        .list_patch_releases(&Client::new(), &release, &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].version, "21.0.2");
+}
+
+#[tokio::test]
+async fn test_downloader_fetch_release_history() {
+    let server = mock_adoptium().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/feature_releases/17/ga"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([adoptium_release(10, 7), adoptium_release(6, 10)])),
+        )
+        .mount(&server)
+        .await;
+
+    let downloader = Downloader::with_providers(vec![
+        Box::new(TemurinProvider::with_base_url(&server.uri())),
+        Box::new(StaticProvider { vendor: "Static" }),
+    ])
+    .with_target(Target::new("linux", "x64"));
+
+    // Static only offers 21, so only Temurin contributes to 17
+    let report = downloader.fetch_release_history(17).await.unwrap();
+
+    let names: Vec<&str> = report.versions.iter().map(|v| v.version.as_str()).collect();
+    assert_eq!(names, vec!["17.0.10+7", "17.0.6+10"]);
+    assert!(report.missing.is_empty());
+}
+
+#[tokio::test]
+async fn test_downloader_release_history_survives_failing_provider() {
+    let server = mock_adoptium().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/feature_releases/17/ga"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([adoptium_release(6, 10)])))
+        .mount(&server)
+        .await;
+    // E.g. GitHub's API rate limit for unauthenticated requests
+    let failing = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&failing)
+        .await;
+
+    let downloader = Downloader::with_providers(vec![
+        Box::new(TemurinProvider::with_base_url(&server.uri())),
+        Box::new(TemurinProvider::with_base_url(&failing.uri())),
+    ])
+    .with_target(Target::new("linux", "x64"))
+    .with_retry_policy(RetryPolicy::none());
+
+    let report = downloader.fetch_release_history(17).await.unwrap();
+    assert_eq!(report.versions.len(), 1);
+    assert_eq!(report.versions[0].version, "17.0.6+10");
+    assert_eq!(report.missing.len(), 1);
+    assert_eq!(report.missing[0].major, Some(17));
+
+    let all_failing = Downloader::with_providers(vec![Box::new(TemurinProvider::with_base_url(
+        &failing.uri(),
+    ))])
+    .with_target(Target::new("linux", "x64"))
+    .with_retry_policy(RetryPolicy::none());
+    assert!(all_failing.fetch_release_history(17).await.is_err());
+}
+
+#[tokio::test]
+async fn test_downloader_release_selection() {
+    let server = mock_adoptium().await;
+    let temurin = || -> Vec<Box<dyn Provider>> {
+        vec![Box::new(TemurinProvider::with_base_url(&server.uri()))]
+    };
+
+    let lts_only = Downloader::with_providers(temurin())
+        .with_target(Target::new("linux", "x64"))
+        .with_selection(ReleaseSelection::AllLts);
+    let versions: Vec<String> = lts_only
+        .fetch_available_versions()
+        .await
+        .unwrap()
+        .versions
+        .into_iter()
+        .map(|v| v.version)
+        .collect();
+    assert_eq!(versions, vec!["17.0.10+7", "21.0.2+13"]);
+
+    let range = Downloader::with_providers(temurin())
+        .with_target(Target::new("linux", "x64"))
+        .with_selection("21..".parse().unwrap());
+    let versions: Vec<String> = range
+        .fetch_available_versions()
+        .await
+        .unwrap()
+        .versions
+        .into_iter()
+        .map(|v| v.version)
+        .collect();
+    assert_eq!(versions, vec!["21.0.2+13", "22.0.1+8"]);
+}
+
+#[tokio::test]
+async fn test_downloader_reports_missing_release() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/info/available_releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "available_releases": [17, 21],
+            "available_lts_releases": [17, 21],
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/latest/21/hotspot"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "binary": { "package": { "link": "https://example.com/jdk.tar.gz", "checksum": "abc" } },
+            "version": { "semver": "21.0.2+13" },
+        }])))
+        .mount(&server)
+        .await;
+
+    let downloader = Downloader::with_providers(vec![Box::new(TemurinProvider::with_base_url(
+        &server.uri(),
+    ))])
+    .with_target(Target::new("linux", "x64"));
+
+    let report = downloader.fetch_available_versions().await.unwrap();
+
+    assert_eq!(report.versions.len(), 1);
+    assert_eq!(report.versions[0].version, "21.0.2+13");
+
+    // Release 17 answers 404 and is reported instead of silently dropped
+    assert_eq!(report.missing.len(), 1);
+    assert_eq!(report.missing[0].vendor, "Eclipse Temurin");
+    assert_eq!(report.missing[0].major, Some(17));
+    assert!(
+        report.missing[0]
+            .to_string()
+            .starts_with("Eclipse Temurin 17: ")
+    );
+}
+
+#[tokio::test]
+async fn test_temurin_resolve_jre_assets() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/latest/21/hotspot"))
+        .and(query_param("image_type", "jre"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "binary": { "package": { "link": "https://example.com/jre.tar.gz", "checksum": "abc" } },
+            "version": { "semver": "21.0.2+13" },
+        }])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = TemurinProvider::with_base_url(&server.uri());
+    let release = Release {
+        major: 21,
+        is_lts: true,
+    };
+    let client = Client::new();
+
+    let target = Target::new("linux", "x64").with_image_type(ImageType::Jre);
+    let assets = provider
+        .resolve_assets(&client, &release, &target)
+        .await
+        .unwrap();
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].image_type, ImageType::Jre);
+
+    // Temurin publishes no headless images, so nothing is requested
+    let target = Target::new("linux", "x64").with_image_type(ImageType::Headless);
+    let assets = provider
+        .resolve_assets(&client, &release, &target)
+        .await
+        .unwrap();
+    assert!(assets.is_empty());
+}
+
+#[tokio::test]
+async fn test_downloader_with_image_type() {
+    let downloader =
+        Downloader::with_providers(vec![Box::new(StaticProvider { vendor: "Static" })])
+            .with_image_type(ImageType::Jre);
+
+    let versions = downloader
+        .fetch_available_versions()
+        .await
+        .unwrap()
+        .versions;
+
+    assert_eq!(versions[0].image_type, ImageType::Jre);
+}
+
+#[tokio::test]
+async fn test_temurin_list_early_access() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/info/available_releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "available_releases": [17, 21, 25],
+            "available_lts_releases": [17, 21, 25],
+            "most_recent_feature_release": 25,
+            "tip_version": 27,
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/feature_releases/26/ea"))
+        .and(query_param("os", "linux"))
+        .and(query_param("architecture", "x64"))
+        .and(query_param("image_type", "jdk"))
+        .and(query_param("page_size", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "binaries": [{
+                "package": {
+                    "link": "https://example.com/OpenJDK26U-jdk_x64_linux_hotspot_ea_26-0-20.tar.gz",
+                    "checksum": "ea26",
+                }
+            }],
+            "version_data": { "semver": "26.0.0-beta+20" },
+        }])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // No build for 27 yet: the API answers 404, which is not an error
+
+    let provider = TemurinProvider::with_base_url(&server.uri());
+    let versions = provider
+        .list_early_access(&Client::new(), &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].version, "26.0.0-beta+20");
+    assert_eq!(versions[0].channel, Channel::Ea);
+    assert!(!versions[0].is_lts);
+    assert_eq!(versions[0].checksum.as_deref(), Some("ea26"));
+}
+
+#[tokio::test]
+async fn test_foojay_list_early_access() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/disco/v3.0/major_versions"))
+        .and(query_param("ea", "true"))
+        .and(query_param("ga", "false"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "result": [{ "major_version": 26, "term_of_support": "STS" }],
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/disco/v3.0/packages"))
+        .and(query_param("version", "26"))
+        .and(query_param("release_status", "ea"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "result": [{
+                "distribution": "temurin",
+                "java_version": "26-ea+20",
+                "javafx_bundled": false,
+                "directly_downloadable": true,
+                "links": {
+                    "pkg_download_redirect": format!("{}/disco/v3.0/ids/ea26/redirect", server.uri()),
+                },
+            }],
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = FoojayProvider::with_base_url(&server.uri());
+    let versions = provider
+        .list_early_access(&Client::new(), &Target::new("linux", "x64"))
+        .await
+        .unwrap();
+
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].version, "26-ea+20");
+    assert_eq!(versions[0].vendor, "Eclipse Temurin");
+    assert_eq!(versions[0].channel, Channel::Ea);
+}
+
+#[tokio::test]
+async fn test_downloader_ea_channel() {
+    let downloader =
+        Downloader::with_providers(vec![Box::new(StaticProvider { vendor: "Static" })])
+            .with_channel(Channel::Ea);
+
+    // Providers without EA builds contribute nothing to the channel
+    assert!(
+        downloader
+            .fetch_available_versions()
+            .await
+            .unwrap()
+            .versions
+            .is_empty()
+    );
+}
@@ -0,0 +1,102 @@
+use jaman::config::{NetworkSettings, ProxySettings};
+use jaman::downloader::Downloader;
+use jaman::providers::{Provider, Target, TemurinProvider};
+use jaman::retry::RetryPolicy;
+use serde_json::json;
+use std::path::PathBuf;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mock_adoptium_releases(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/v3/info/available_releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "available_releases": [],
+            "available_lts_releases": [],
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn downloader_for(base_url: &str) -> Downloader {
+    let providers: Vec<Box<dyn Provider>> =
+        vec![Box::new(TemurinProvider::with_base_url(base_url))];
+    Downloader::with_providers(providers)
+        .with_target(Target::new("linux", "x64"))
+        .with_network(NetworkSettings {
+            connect_timeout_secs: 1,
+            ..NetworkSettings::default()
+        })
+        .with_retry_policy(RetryPolicy::none())
+}
+
+#[tokio::test]
+async fn test_requests_go_through_proxy_with_credentials() {
+    let proxy = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/info/available_releases"))
+        .and(header("proxy-authorization", "Basic dXNlcjpzZWNyZXQ="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "available_releases": [],
+            "available_lts_releases": [],
+        })))
+        .expect(1)
+        .mount(&proxy)
+        .await;
+
+    // The API host does not resolve, so only the proxy can answer
+    let downloader = downloader_for("http://api.adoptium.invalid")
+        .with_proxy(&ProxySettings {
+            url: Some(proxy.uri()),
+            username: Some("user".to_string()),
+            password: Some("secret".to_string()),
+            no_proxy: Vec::new(),
+        })
+        .unwrap();
+
+    let report = downloader.fetch_available_versions().await.unwrap();
+    assert!(report.versions.is_empty());
+    assert!(report.missing.is_empty());
+}
+
+#[tokio::test]
+async fn test_no_proxy_hosts_are_reached_directly() {
+    let server = MockServer::start().await;
+    mock_adoptium_releases(&server).await;
+
+    // Nothing listens on the discard port, so proxied requests would fail
+    let downloader = downloader_for(&server.uri())
+        .with_proxy(&ProxySettings {
+            url: Some("http://127.0.0.1:9".to_string()),
+            no_proxy: vec!["127.0.0.1".to_string()],
+            ..ProxySettings::default()
+        })
+        .unwrap();
+
+    assert!(downloader.fetch_available_versions().await.is_ok());
+}
+
+#[test]
+fn test_invalid_proxy_url() {
+    let result = Downloader::new().with_proxy(&ProxySettings {
+        url: Some("not a url".to_string()),
+        ..ProxySettings::default()
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_ca_certificates() {
+    let bundle = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls/ca.pem");
+    assert!(Downloader::new().with_ca_certificates(&[bundle]).is_ok());
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let garbage = temp_dir.path().join("garbage.pem");
+    std::fs::write(&garbage, "not a certificate").unwrap();
+    assert!(Downloader::new().with_ca_certificates(&[garbage]).is_err());
+
+    let missing = temp_dir.path().join("missing.pem");
+    assert!(Downloader::new().with_ca_certificates(&[missing]).is_err());
+}
@@ -0,0 +1,176 @@
+use jaman::config::{Channel, ImageType};
+use jaman::downloader::AvailableVersion;
+use jaman::query::VersionQuery;
+
+fn available(version: &str, vendor: &str, is_lts: bool) -> AvailableVersion {
+    AvailableVersion {
+        version: version.to_string(),
+        vendor: vendor.to_string(),
+        is_lts,
+        architecture: "x64".to_string(),
+        download_url: format!("https://example.com/{}.tar.gz", version),
+        checksum: None,
+        javafx_bundled: false,
+        image_type: ImageType::Jdk,
+        channel: Channel::Ga,
+    }
+}
+
+fn query(text: &str) -> VersionQuery {
+    text.parse().unwrap()
+}
+
+fn catalog() -> Vec<AvailableVersion> {
+    vec![
+        available("11.0.22+7", "Eclipse Temurin", true),
+        available("17.0.7+7", "Eclipse Temurin", true),
+        available("17.0.10+7", "Eclipse Temurin", true),
+        available("21.0.7+6", "Eclipse Temurin", true),
+        available("21.0.7+6", "Azul Zulu", true),
+        available("22.0.2+9", "Eclipse Temurin", false),
+        available("21.0.2.13.1", "Amazon Corretto", true),
+    ]
+}
+
+fn newest(text: &str) -> Option<String> {
+    let versions = catalog();
+    query(text)
+        .select(&versions)
+        .unwrap()
+        .map(|v| format!("{} {}", v.vendor, v.version))
+}
+
+#[test]
+fn test_feature_release_matches_whole_components() {
+    assert_eq!(newest("17").as_deref(), Some("Eclipse Temurin 17.0.10+7"));
+    assert_eq!(newest("1"), None);
+    assert_eq!(newest("7"), None);
+    // A leading "1" is not a prefix of 11, 17 or 21
+    assert!(!query("1").matches(&available("11.0.22+7", "Eclipse Temurin", true)));
+}
+
+#[test]
+fn test_patch_and_build_queries() {
+    let version = available("17.0.6+10", "Eclipse Temurin", true);
+
+    assert!(query("17.0.6").matches(&version));
+    assert!(query("17.0.6+10").matches(&version));
+    assert!(query("jdk-17.0.6+10").matches(&version));
+    assert!(query("17.0").matches(&version));
+    assert!(!query("17.0.60").matches(&version));
+    assert!(!query("17.0.6+9").matches(&version));
+    assert!(!query("17.0.0").matches(&version));
+
+    assert!(query("17.0.6").is_patch());
+    assert!(query("temurin@17.0.6+10").is_patch());
+    assert!(!query("17").is_patch());
+    assert!(!query("corretto-21").is_patch());
+
+    assert_eq!(query("17.0.6+10").feature(), Some(17));
+    assert_eq!(query("corretto-21").feature(), Some(21));
+    assert_eq!(query("latest").feature(), None);
+    assert_eq!(query(">=17").feature(), None);
+}
+
+#[test]
+fn test_ranges() {
+    let versions = catalog();
+    let matching = |text: &str| -> Vec<String> {
+        let query = query(text);
+        versions
+            .iter()
+            .filter(|v| query.matches(*v))
+            .map(|v| v.version.clone())
+            .collect()
+    };
+
+    assert_eq!(
+        matching(">=17 <21"),
+        vec!["17.0.7+7".to_string(), "17.0.10+7".to_string()]
+    );
+    assert_eq!(matching(">= 17, <= 17"), matching("17"));
+    assert_eq!(matching(">21"), vec!["22.0.2+9".to_string()]);
+    assert_eq!(
+        newest(">=17 <21").as_deref(),
+        Some("Eclipse Temurin 17.0.10+7")
+    );
+}
+
+#[test]
+fn test_keywords() {
+    assert_eq!(
+        newest("latest").as_deref(),
+        Some("Eclipse Temurin 22.0.2+9")
+    );
+    // Temurin and Zulu both ship the newest LTS build
+    assert!(query("lts").select(&catalog()).is_err());
+    assert_eq!(
+        newest("temurin@LTS").as_deref(),
+        Some("Eclipse Temurin 21.0.7+6")
+    );
+    assert_eq!(
+        newest("corretto@lts").as_deref(),
+        Some("Amazon Corretto 21.0.2.13.1")
+    );
+}
+
+#[test]
+fn test_vendor_qualifiers() {
+    assert_eq!(newest("zulu@21").as_deref(), Some("Azul Zulu 21.0.7+6"));
+    assert_eq!(newest("Zulu-21").as_deref(), Some("Azul Zulu 21.0.7+6"));
+    assert_eq!(
+        newest("corretto-21.0.2").as_deref(),
+        Some("Amazon Corretto 21.0.2.13.1")
+    );
+    assert_eq!(
+        newest("temurin@11").as_deref(),
+        Some("Eclipse Temurin 11.0.22+7")
+    );
+    assert_eq!(newest("corretto-17"), None);
+}
+
+#[test]
+fn test_select_lists_ambiguous_candidates() {
+    let versions = catalog();
+
+    let error = query("21.0.7").select(&versions).unwrap_err().to_string();
+    assert!(error.contains("ambiguous"));
+    assert!(error.contains("21.0.7+6 - Eclipse Temurin"));
+    assert!(error.contains("21.0.7+6 - Azul Zulu"));
+    assert!(error.contains("<vendor>@21.0.7"));
+
+    let selected = query("temurin@21.0.7").select(&versions).unwrap().unwrap();
+    assert_eq!(selected.vendor, "Eclipse Temurin");
+    assert!(query("17").select(&versions).unwrap().is_some());
+    assert!(query("9").select(&versions).unwrap().is_none());
+
+    // Available versions have no installation IDs to choose by
+    assert!(!error.contains("jaman list"));
+}
+
+#[test]
+fn test_vendor_suffixes_do_not_break_ties() {
+    // Temurin's "+13.0.LTS" and Corretto's "+13" name the same release
+    let versions = vec![
+        available("21.0.2+13.0.LTS", "Eclipse Temurin", true),
+        available("21.0.2+13", "Amazon Corretto", true),
+    ];
+
+    let error = query("21").select(&versions).unwrap_err().to_string();
+    assert!(error.contains("21.0.2+13.0.LTS - Eclipse Temurin"));
+    assert!(error.contains("21.0.2+13 - Amazon Corretto"));
+
+    let selected = query("corretto@21").select(&versions).unwrap().unwrap();
+    assert_eq!(selected.vendor, "Amazon Corretto");
+}
+
+#[test]
+fn test_invalid_queries() {
+    for invalid in ["", "@21", "zulu@", "banana", ">=", "21..2", ">=17 <x"] {
+        assert!(
+            invalid.parse::<VersionQuery>().is_err(),
+            "{} was parsed",
+            invalid
+        );
+    }
+}
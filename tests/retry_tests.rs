@@ -0,0 +1,164 @@
+use jaman::config::NetworkSettings;
+use jaman::downloader::Downloader;
+use jaman::providers::{Target, TemurinProvider};
+use jaman::retry::{ReadTimeout, RetryPolicy, is_transient};
+use serde_json::json;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn fast_network() -> NetworkSettings {
+    NetworkSettings {
+        connect_timeout_secs: 1,
+        read_timeout_secs: 1,
+        retries: 2,
+        backoff_ms: 1,
+    }
+}
+
+#[test]
+fn test_backoff_doubles_up_to_cap() {
+    let policy = RetryPolicy::from_settings(&NetworkSettings::default());
+
+    assert_eq!(policy.retries, 3);
+    assert_eq!(policy.backoff_for(0), Duration::from_millis(500));
+    assert_eq!(policy.backoff_for(1), Duration::from_millis(1000));
+    assert_eq!(policy.backoff_for(2), Duration::from_millis(2000));
+    assert_eq!(policy.backoff_for(20), Duration::from_secs(30));
+}
+
+#[tokio::test]
+async fn test_retry_stops_on_permanent_error() {
+    let policy = RetryPolicy::from_settings(&fast_network());
+    let attempts = AtomicU32::new(0);
+
+    let result: anyhow::Result<()> = policy
+        .run(|| async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(anyhow::anyhow!("Version 99 not found"))
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_retries() {
+    let policy = RetryPolicy::from_settings(&fast_network());
+    let attempts = AtomicU32::new(0);
+
+    let result: anyhow::Result<()> = policy
+        .run(|| async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(ReadTimeout(Duration::from_secs(1)).into())
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_server_errors_are_transient() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/unavailable"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let client = reqwest::Client::new();
+    let status_error = |url: String| {
+        let client = client.clone();
+        async move {
+            anyhow::Error::from(
+                client
+                    .get(url)
+                    .send()
+                    .await
+                    .unwrap()
+                    .error_for_status()
+                    .unwrap_err(),
+            )
+        }
+    };
+
+    assert!(is_transient(
+        &status_error(format!("{}/unavailable", server.uri())).await
+    ));
+    // Unmatched requests answer 404, which retrying will not fix
+    assert!(!is_transient(
+        &status_error(format!("{}/missing", server.uri())).await
+    ));
+}
+
+#[tokio::test]
+async fn test_downloader_retries_transient_failure() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/info/available_releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "available_releases": [21],
+            "available_lts_releases": [21],
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/latest/21/hotspot"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/assets/latest/21/hotspot"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "binary": { "package": { "link": "https://example.com/jdk.tar.gz", "checksum": "abc" } },
+            "version": { "semver": "21.0.2+13" },
+        }])))
+        .mount(&server)
+        .await;
+
+    let downloader = Downloader::with_providers(vec![Box::new(TemurinProvider::with_base_url(
+        &server.uri(),
+    ))])
+    .with_target(Target::new("linux", "x64"))
+    .with_network(fast_network());
+
+    let report = downloader.fetch_available_versions().await.unwrap();
+
+    assert_eq!(report.versions.len(), 1);
+    assert!(report.missing.is_empty());
+}
+
+#[tokio::test]
+async fn test_download_read_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jdk.tar.gz"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_bytes(b"archive".to_vec())
+                .set_delay(Duration::from_secs(3)),
+        )
+        .mount(&server)
+        .await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let downloader = Downloader::new()
+        .with_network(fast_network())
+        .with_retry_policy(RetryPolicy::none());
+
+    let error = downloader
+        .install_from_url(
+            &format!("{}/jdk.tar.gz", server.uri()),
+            None,
+            temp_dir.path(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(error.is::<ReadTimeout>());
+}
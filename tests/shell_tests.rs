@@ -0,0 +1,278 @@
+use jaman::shell::{self, CurrentEnv, EnvChange, Shell};
+use std::path::Path;
+
+fn env(path: &str, java_home: Option<&str>, jaman_java_home: Option<&str>) -> CurrentEnv {
+    CurrentEnv {
+        path: Some(path.to_string()),
+        java_home: java_home.map(str::to_string),
+        jaman_java_home: jaman_java_home.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn value<'a>(changes: &'a [EnvChange], name: &str) -> Option<Option<&'a str>> {
+    changes
+        .iter()
+        .find(|change| change.name == name)
+        .map(|change| change.value.as_deref())
+}
+
+#[test]
+fn test_parse_shell() {
+    assert_eq!("bash".parse::<Shell>().unwrap(), Shell::Bash);
+    assert_eq!("PowerShell".parse::<Shell>().unwrap(), Shell::Pwsh);
+    assert_eq!("nu".parse::<Shell>().unwrap(), Shell::Nu);
+    assert_eq!("cmd".parse::<Shell>().unwrap(), Shell::Cmd);
+    assert!("tcsh".parse::<Shell>().is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_switching_replaces_previous_jdk_on_path() {
+    let current = env(
+        "/jdks/temurin-21/bin:/usr/local/bin:/usr/bin",
+        Some("/jdks/temurin-21"),
+        Some("/jdks/temurin-21"),
+    );
+
+    let changes = shell::java_home_changes(Some(Path::new("/jdks/corretto-17")), &current).unwrap();
+    assert_eq!(
+        value(&changes, "JAVA_HOME"),
+        Some(Some("/jdks/corretto-17"))
+    );
+    assert_eq!(
+        value(&changes, "JAMAN_JAVA_HOME"),
+        Some(Some("/jdks/corretto-17"))
+    );
+    assert_eq!(
+        value(&changes, "PATH"),
+        Some(Some("/jdks/corretto-17/bin:/usr/local/bin:/usr/bin"))
+    );
+
+    // Nothing to do when the shell is already on that JDK
+    let changes = shell::java_home_changes(Some(Path::new("/jdks/temurin-21")), &current).unwrap();
+    assert!(changes.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_activation_changes_are_complete() {
+    let current = env(
+        "/jdks/temurin-21/bin:/usr/bin",
+        Some("/jdks/temurin-21"),
+        Some("/jdks/temurin-21"),
+    );
+
+    // Unlike the hook, env statements are printed even if nothing changes,
+    // and the JDK's bin directory is not added twice
+    let changes = shell::activation_changes(Path::new("/jdks/temurin-21"), &current).unwrap();
+    assert_eq!(value(&changes, "JAVA_HOME"), Some(Some("/jdks/temurin-21")));
+    assert_eq!(
+        value(&changes, "PATH"),
+        Some(Some("/jdks/temurin-21/bin:/usr/bin"))
+    );
+    assert_eq!(
+        value(&changes, "JAMAN_JAVA_HOME"),
+        Some(Some("/jdks/temurin-21"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_leaving_a_project_restores_the_environment() {
+    let current = env(
+        "/jdks/temurin-21/bin:/usr/bin",
+        Some("/jdks/temurin-21"),
+        Some("/jdks/temurin-21"),
+    );
+    let changes = shell::java_home_changes(None, &current).unwrap();
+    assert_eq!(value(&changes, "JAVA_HOME"), Some(None));
+    assert_eq!(value(&changes, "JAMAN_JAVA_HOME"), Some(None));
+    assert_eq!(value(&changes, "PATH"), Some(Some("/usr/bin")));
+
+    // A JAVA_HOME jaman did not set is left alone
+    let current = env("/usr/bin", Some("/opt/java"), None);
+    assert!(shell::java_home_changes(None, &current).unwrap().is_empty());
+    let current = env(
+        "/jdks/temurin-21/bin:/usr/bin",
+        Some("/opt/java"),
+        Some("/jdks/temurin-21"),
+    );
+    let changes = shell::java_home_changes(None, &current).unwrap();
+    assert_eq!(value(&changes, "JAVA_HOME"), None);
+}
+
+#[test]
+fn test_render_quotes_values() {
+    let changes = vec![
+        EnvChange {
+            name: "JAVA_HOME".to_string(),
+            value: Some("/opt/it's java".to_string()),
+        },
+        EnvChange {
+            name: "JAMAN_JAVA_HOME".to_string(),
+            value: None,
+        },
+    ];
+
+    assert_eq!(
+        shell::render(Shell::Bash, &changes),
+        "export JAVA_HOME='/opt/it'\\''s java';\nunset JAMAN_JAVA_HOME;"
+    );
+    assert_eq!(
+        shell::render(Shell::Fish, &changes),
+        "set -gx JAVA_HOME '/opt/it\\'s java';\nset -e JAMAN_JAVA_HOME;"
+    );
+    assert_eq!(
+        shell::render(Shell::Pwsh, &changes),
+        "$env:JAVA_HOME = '/opt/it''s java';\n\
+         Remove-Item -Path Env:JAMAN_JAVA_HOME -ErrorAction SilentlyContinue;"
+    );
+
+    let nu: serde_json::Value = serde_json::from_str(&shell::render(Shell::Nu, &changes)).unwrap();
+    assert_eq!(nu["set"]["JAVA_HOME"], "/opt/it's java");
+    assert_eq!(nu["unset"][0], "JAMAN_JAVA_HOME");
+    assert_eq!(
+        shell::render(Shell::Cmd, &changes),
+        "set \"JAVA_HOME=/opt/it's java\"\nset \"JAMAN_JAVA_HOME=\""
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&shell::render(Shell::Json, &changes)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "JAVA_HOME": "/opt/it's java", "JAMAN_JAVA_HOME": null })
+    );
+    assert_eq!(shell::render(Shell::Bash, &[]), "");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_render_path_as_list_for_fish_and_nu() {
+    let changes = vec![EnvChange {
+        name: "PATH".to_string(),
+        value: Some("/jdk/bin:/usr/bin".to_string()),
+    }];
+
+    assert_eq!(
+        shell::render(Shell::Fish, &changes),
+        "set -gx PATH '/jdk/bin' '/usr/bin';"
+    );
+    let nu: serde_json::Value = serde_json::from_str(&shell::render(Shell::Nu, &changes)).unwrap();
+    assert_eq!(
+        nu["set"]["PATH"],
+        serde_json::json!(["/jdk/bin", "/usr/bin"])
+    );
+}
+
+#[test]
+fn test_hooks_call_back_into_jaman() {
+    let jaman = Path::new("/opt/jaman/bin/jaman");
+    for (shell, call) in [
+        (Shell::Bash, "'/opt/jaman/bin/jaman' hook bash --export"),
+        (Shell::Zsh, "'/opt/jaman/bin/jaman' hook zsh --export"),
+        (Shell::Fish, "'/opt/jaman/bin/jaman' hook fish --export"),
+        (Shell::Pwsh, "& '/opt/jaman/bin/jaman' hook pwsh --export"),
+        (Shell::Nu, "^\"/opt/jaman/bin/jaman\" hook nu --export"),
+    ] {
+        let script = shell::hook(shell, jaman).unwrap();
+        assert!(script.contains(call), "{:?} hook:\n{}", shell, script);
+        assert!(script.contains("JAMAN_SHELL"));
+    }
+    assert!(shell::hook(Shell::Cmd, jaman).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bash_hook_is_valid_and_idempotent() {
+    let Ok(output) = std::process::Command::new("bash")
+        .arg("-c")
+        .arg(format!(
+            "{hook}\n{hook}\necho \"$PROMPT_COMMAND\"",
+            hook = shell::hook(Shell::Bash, Path::new("/bin/true")).unwrap()
+        ))
+        .env("PROMPT_COMMAND", "history -a")
+        .output()
+    else {
+        // bash is not installed
+        return;
+    };
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "_jaman_hook;history -a"
+    );
+}
+
+/// Run the jaman binary with its config under `home`, in `dir`
+#[cfg(target_os = "linux")]
+fn run_jaman(home: &Path, dir: &Path, vars: &[(String, String)], args: &[&str]) -> String {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_jaman"))
+        .args(args)
+        .current_dir(dir)
+        .env_clear()
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_DATA_HOME", home.join(".local/share"))
+        .env("PATH", "/usr/bin:/bin")
+        .envs(vars.iter().cloned())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "jaman {:?}: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_hook_keeps_jdk_chosen_with_env() {
+    use jaman::config::{Config, JavaVersion};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let home = temp_dir.path();
+    let mut config = Config::new(home.join("jdks"), home.join("downloads"));
+    let mut ids = Vec::new();
+    for version in ["17.0.10", "21.0.2"] {
+        ids.push(config.add_version(JavaVersion::new(
+            version.to_string(),
+            "Eclipse Temurin".to_string(),
+            home.join("jdks").join(version),
+            true,
+            "x64".to_string(),
+            false,
+        )));
+    }
+    config.set_active(&ids[0]).unwrap();
+    std::fs::create_dir_all(home.join(".config/jaman")).unwrap();
+    config
+        .save_to(&home.join(".config/jaman/config.toml"))
+        .unwrap();
+
+    // Apply `eval "$(jaman env 21)"` to the variables of a hooked shell
+    let json = run_jaman(home, home, &[], &["env", "21", "--shell", "json"]);
+    let exported: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&json).unwrap();
+    let mut vars: Vec<(String, String)> = exported
+        .into_iter()
+        .map(|(name, value)| (name, value.as_str().unwrap().to_string()))
+        .collect();
+    vars.push(("JAMAN_SHELL".to_string(), "bash".to_string()));
+    let jdk21 = home.join("jdks/21.0.2").display().to_string();
+    assert!(vars.contains(&("JAVA_HOME".to_string(), jdk21.clone())));
+
+    // The next prompt keeps the session's JDK
+    let export = run_jaman(home, home, &vars, &["hook", "bash", "--export"]);
+    assert_eq!(export.trim(), "");
+
+    // Without the session override the hook goes back to the active version
+    vars.retain(|(name, _)| name != "JAMAN_SESSION_HOME");
+    let export = run_jaman(home, home, &vars, &["hook", "bash", "--export"]);
+    assert!(export.contains(&format!(
+        "export JAVA_HOME='{}'",
+        home.join("jdks/17.0.10").display()
+    )));
+}
@@ -0,0 +1,142 @@
+use jaman::config::JavaVersion;
+use jaman::version::{Version, compare, compare_ignore_optional};
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+fn v(s: &str) -> Version {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_parse_jep_223() {
+    let version = v("17.0.10+7-LTS");
+    assert_eq!(version.feature(), 17);
+    assert_eq!(version.interim(), 0);
+    assert_eq!(version.update(), 10);
+    assert_eq!(version.patch(), 0);
+    assert_eq!(version.build, Some(7));
+    assert_eq!(version.pre, None);
+    assert_eq!(version.optional.as_deref(), Some("LTS"));
+
+    let ea = v("26-ea+18");
+    assert_eq!(ea.feature(), 26);
+    assert_eq!(ea.pre.as_deref(), Some("ea"));
+    assert_eq!(ea.build, Some(18));
+
+    let temurin_ea = v("26.0.0-beta+18.0.202510171234");
+    assert_eq!(temurin_ea.pre.as_deref(), Some("beta"));
+    assert_eq!(temurin_ea.build, Some(18));
+
+    assert_eq!(v("17.0.9.1").patch(), 1);
+    assert_eq!(v("jdk-21.0.2+13"), v("21.0.2+13"));
+}
+
+#[test]
+fn test_parse_legacy_scheme() {
+    let version = v("1.8.0_292-b10");
+    assert_eq!(version.feature(), 8);
+    assert_eq!(version.update(), 292);
+    assert_eq!(version.build, Some(10));
+    assert_eq!(version.pre, None);
+
+    assert_eq!(v("1.8.0_292"), v("8.0.292"));
+    assert_eq!(v("8u292"), v("8.0.292"));
+    // The form the detector normalizes `release` files to
+    assert_eq!(v("8.0_292"), v("8.0.292"));
+}
+
+#[test]
+fn test_parse_rejects_non_versions() {
+    for invalid in ["", "ea", "latest", "21..2", "21-"] {
+        assert!(
+            invalid.parse::<Version>().is_err(),
+            "{} was parsed",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_ordering() {
+    let mut versions = vec![
+        "9.0.4",
+        "21.0.2+13",
+        "17.0.9+9",
+        "17.0.10+7",
+        "21",
+        "1.8.0_292-b10",
+        "26-ea+18",
+        "26-ea+9",
+        "26+35",
+    ];
+    versions.sort_by(|a, b| compare(a, b));
+
+    assert_eq!(
+        versions,
+        vec![
+            "1.8.0_292-b10",
+            "9.0.4",
+            "17.0.9+9",
+            "17.0.10+7",
+            "21",
+            "21.0.2+13",
+            "26-ea+9",
+            "26-ea+18",
+            "26+35",
+        ]
+    );
+}
+
+#[test]
+fn test_trailing_zeros_are_insignificant() {
+    assert_eq!(v("21"), v("21.0.0"));
+    assert_eq!(v("21.0.0").cmp(&v("21")), Ordering::Equal);
+    assert!(v("21.0.1") > v("21"));
+    // Builds order within the same version number
+    assert!(v("21+35") > v("21"));
+    assert!(v("21.0.2+13") > v("21.0.2+3"));
+}
+
+#[test]
+fn test_compare_falls_back_for_unparsed_strings() {
+    assert_eq!(compare("21.0.2", "unknown"), Ordering::Greater);
+    assert_eq!(compare("unknown", "other"), "unknown".cmp("other"));
+}
+
+#[test]
+fn test_java_version_cmp_version() {
+    let java = |version: &str| {
+        JavaVersion::new(
+            version.to_string(),
+            "Eclipse Temurin".to_string(),
+            PathBuf::from("/test").join(version),
+            true,
+            "x64".to_string(),
+            false,
+        )
+    };
+
+    assert_eq!(
+        java("17.0.10").cmp_version(&java("17.0.9")),
+        Ordering::Greater
+    );
+    assert_eq!(java("9.0.4").cmp_version(&java("21.0.2")), Ordering::Less);
+}
+
+#[test]
+fn test_optional_part() {
+    assert_eq!(
+        compare_ignore_optional("21.0.2+13.0.LTS", "21.0.2+13"),
+        Ordering::Equal
+    );
+    assert_eq!(
+        compare_ignore_optional("21.0.2+14", "21.0.2+13.0.LTS"),
+        Ordering::Greater
+    );
+    assert_ne!(compare("21.0.2+13.0.LTS", "21.0.2+13"), Ordering::Equal);
+
+    // Displayed as the vendor wrote it
+    for text in ["21.0.2+13.0.LTS", "17.0.10+7-LTS", "26-ea+18", "21.0.2+13"] {
+        assert_eq!(v(text).to_string(), text);
+    }
+}